@@ -0,0 +1,387 @@
+use crate::{
+    Cutter, ErrReport, Genre, Note, NotePart, Prefix, Prefixes, Second, Segment, Year, LC,
+};
+use serde::de::{self, MapAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::fmt;
+use std::ops::Range;
+
+/// An owned `LC`, for keeping call numbers around after the text they were
+/// parsed from is gone, e.g. to cache them or send them to another thread.
+///
+/// `LCBuf` holds the canonical text of the call number along with where each
+/// component sits in it, and borrows it back out as an `LC` with `as_lc`
+/// without parsing it again. It compares in shelf order, like `LC`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LCBuf {
+    text: String,
+    layout: Layout,
+}
+
+/// Where each borrowed piece of an `LC` sits in the text of an `LCBuf`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Layout {
+    prefix: Option<Range<usize>>,
+    nlm: bool,
+    genre: Range<usize>,
+    integer: Range<usize>,
+    fraction: Option<Range<usize>>,
+    segments: Vec<SegmentLayout>,
+    year: Option<Year>,
+    note: Option<NoteLayout>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum SegmentLayout {
+    Cutter {
+        has_dot: bool,
+        letters: Range<usize>,
+        digits: Range<usize>,
+        suffix: Option<Range<usize>>,
+    },
+    Year(Year),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct NoteLayout {
+    text: Range<usize>,
+    parts: Vec<PartLayout>,
+}
+
+/// A `NotePart`, with the text of `Other` replaced by where it sits.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum PartLayout {
+    Recognized(NotePart<'static>),
+    Other(Range<usize>),
+}
+
+/// The components of an `LC` as plain strings, the object form used by the
+/// `components` serde module.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub(crate) struct Components {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    prefix: Option<String>,
+    class: String,
+    /// Ignored when deserializing since it follows from `class`
+    #[serde(default, skip_deserializing)]
+    nlm: bool,
+    class_number: String,
+    segments: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    year: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    note: Option<String>,
+}
+
+impl LCBuf {
+    /// Borrows this call number as an `LC`.
+    pub fn as_lc(&self) -> LC<'_> {
+        let at = |range: &Range<usize>| &self.text[range.clone()];
+        let layout = &self.layout;
+
+        let segments = layout
+            .segments
+            .iter()
+            .map(|segment| match segment {
+                SegmentLayout::Cutter {
+                    has_dot,
+                    letters,
+                    digits,
+                    suffix,
+                } => Segment::Cutter(Cutter {
+                    has_dot: *has_dot,
+                    letters: at(letters),
+                    digits: at(digits),
+                    suffix: suffix.as_ref().map(at),
+                }),
+                SegmentLayout::Year(year) => Segment::Year(*year),
+            })
+            .collect();
+
+        let note = layout.note.as_ref().map(|note| Note {
+            parts: note
+                .parts
+                .iter()
+                .map(|part| match part {
+                    PartLayout::Recognized(part) => *part,
+                    PartLayout::Other(other) => NotePart::Other(at(other)),
+                })
+                .collect(),
+            text: at(&note.text),
+        });
+
+        LC {
+            prefix: layout.prefix.as_ref().map(|prefix| Prefix(at(prefix))),
+            nlm: layout.nlm,
+            genre: Genre(at(&layout.genre)),
+            second: Second {
+                integer: at(&layout.integer),
+                fraction: layout.fraction.as_ref().map(at),
+            },
+            segments,
+            year: layout.year,
+            note,
+        }
+    }
+
+    /// The canonical text of this call number, as `LC`'s `Display` writes it.
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    fn parse(i: &str) -> Result<Self, ErrReport> {
+        match LC::maybe_parse(i)? {
+            Some(lc) => LCBuf::try_from(&lc),
+            None => Err(ErrReport::msg("missing call number")),
+        }
+    }
+
+    fn from_components(components: Components) -> Result<Self, ErrReport> {
+        let mut prefixes = Prefixes::none();
+        prefixes.extend(components.prefix.clone());
+
+        let text = components
+            .prefix
+            .iter()
+            .chain(Some(&components.class))
+            .chain(Some(&components.class_number))
+            .chain(&components.segments)
+            .chain(&components.year)
+            .chain(&components.note)
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        let lc = match LC::maybe_parse_with(&text, &prefixes)? {
+            Some(lc) => lc,
+            None => return Err(ErrReport::msg("missing call number")),
+        };
+
+        // Parsing the joined text could split it up differently, e.g. when a
+        // segment holds two cutters, so the parts have to round trip as given
+        let parsed = Components::from(&lc);
+        let components = Components {
+            nlm: parsed.nlm,
+            ..components
+        };
+        if parsed != components {
+            return Err(ErrReport::msg(format!(
+                "components {:?} don't match how they parse together: {:?}",
+                components, parsed
+            )));
+        }
+
+        LCBuf::try_from(&lc)
+    }
+}
+
+impl Layout {
+    /// Records where the pieces of `lc` sit in `text`, which they all borrow
+    /// from.
+    fn new(text: &str, lc: &LC<'_>) -> Self {
+        let at = |part: &str| {
+            let start = part.as_ptr() as usize - text.as_ptr() as usize;
+            start..start + part.len()
+        };
+
+        let segments = lc
+            .segments
+            .iter()
+            .map(|segment| match segment {
+                Segment::Cutter(cutter) => SegmentLayout::Cutter {
+                    has_dot: cutter.has_dot,
+                    letters: at(cutter.letters),
+                    digits: at(cutter.digits),
+                    suffix: cutter.suffix.map(at),
+                },
+                Segment::Year(year) => SegmentLayout::Year(*year),
+            })
+            .collect();
+
+        let note = lc.note.as_ref().map(|note| NoteLayout {
+            text: at(note.text),
+            parts: note
+                .parts
+                .iter()
+                .map(|part| match *part {
+                    NotePart::Volume(volume) => PartLayout::Recognized(NotePart::Volume(volume)),
+                    NotePart::Part(part) => PartLayout::Recognized(NotePart::Part(part)),
+                    NotePart::Number(number) => PartLayout::Recognized(NotePart::Number(number)),
+                    NotePart::Supplement(supplement) => {
+                        PartLayout::Recognized(NotePart::Supplement(supplement))
+                    }
+                    NotePart::Index => PartLayout::Recognized(NotePart::Index),
+                    NotePart::Years { start, end } => {
+                        PartLayout::Recognized(NotePart::Years { start, end })
+                    }
+                    NotePart::Copy(copy) => PartLayout::Recognized(NotePart::Copy(copy)),
+                    NotePart::Other(other) => PartLayout::Other(at(other)),
+                })
+                .collect(),
+        });
+
+        Layout {
+            prefix: lc.prefix.as_ref().map(|prefix| at(prefix.0)),
+            nlm: lc.nlm,
+            genre: at(lc.genre.0),
+            integer: at(lc.second.integer),
+            fraction: lc.second.fraction.map(at),
+            segments,
+            year: lc.year,
+            note,
+        }
+    }
+}
+
+impl<'a> LC<'a> {
+    /// Copies this call number into an owned `LCBuf`, see `LCBuf::try_from`.
+    pub fn to_buf(&self) -> Result<LCBuf, ErrReport> {
+        LCBuf::try_from(self)
+    }
+}
+
+impl<'a> TryFrom<&LC<'a>> for LCBuf {
+    type Error = ErrReport;
+
+    /// Fails when `lc` doesn't parse back into the same `LC` from the text it
+    /// displays as, which can happen when its fields were set by hand.
+    fn try_from(lc: &LC<'a>) -> Result<Self, Self::Error> {
+        let text = lc.to_string();
+
+        // The prefix may not be part of the default vocabulary
+        let mut prefixes = Prefixes::none();
+        prefixes.extend(lc.prefix.as_ref().map(Prefix::as_str));
+
+        let layout = match LC::maybe_parse_with(&text, &prefixes)? {
+            Some(ref parsed) if parsed == lc => Layout::new(&text, parsed),
+            _ => {
+                return Err(ErrReport::msg(format!(
+                    "{:?} doesn't parse back into the same call number",
+                    text
+                )))
+            }
+        };
+
+        Ok(LCBuf { text, layout })
+    }
+}
+
+impl<'a> TryFrom<LC<'a>> for LCBuf {
+    type Error = ErrReport;
+
+    fn try_from(lc: LC<'a>) -> Result<Self, Self::Error> {
+        LCBuf::try_from(&lc)
+    }
+}
+
+impl<'a> From<&LC<'a>> for Components {
+    fn from(lc: &LC<'a>) -> Self {
+        Components {
+            prefix: lc.prefix.as_ref().map(ToString::to_string),
+            class: lc.genre.0.to_string(),
+            nlm: lc.nlm,
+            class_number: lc.second.to_string(),
+            segments: lc.segments.iter().map(ToString::to_string).collect(),
+            year: lc.year.as_ref().map(ToString::to_string),
+            note: lc.note.as_ref().map(ToString::to_string),
+        }
+    }
+}
+
+impl PartialOrd for LCBuf {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for LCBuf {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_lc().cmp(&other.as_lc())
+    }
+}
+
+impl fmt::Display for LCBuf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.text)
+    }
+}
+
+impl<'a> Serialize for LC<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de: 'a, 'a> Deserialize<'de> for LC<'a> {
+    /// Borrows from the input, so only the canonical string form can be
+    /// deserialized into an `LC`. Use `LCBuf` for the object form.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let i = <&'de str>::deserialize(deserializer)?;
+
+        match LC::maybe_parse(i) {
+            Ok(Some(lc)) => Ok(lc),
+            Ok(None) => Err(de::Error::custom("missing call number")),
+            Err(e) => Err(de::Error::custom(e.reason())),
+        }
+    }
+}
+
+impl Serialize for LCBuf {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.text)
+    }
+}
+
+impl<'de> Deserialize<'de> for LCBuf {
+    /// Accepts either the canonical string form or the object form written
+    /// by the `components` serde module.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(LCBufVisitor)
+    }
+}
+
+struct LCBufVisitor;
+
+impl<'de> Visitor<'de> for LCBufVisitor {
+    type Value = LCBuf;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "an LC call number as a string or an object of its components"
+        )
+    }
+
+    fn visit_str<E: de::Error>(self, i: &str) -> Result<LCBuf, E> {
+        LCBuf::parse(i).map_err(|e| E::custom(e.reason()))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<LCBuf, A::Error> {
+        let components = Components::deserialize(de::value::MapAccessDeserializer::new(map))?;
+        LCBuf::from_components(components).map_err(|e| de::Error::custom(e.reason()))
+    }
+}
+
+/// Serializes an `LCBuf` as an object of its components rather than as its
+/// canonical string, for use with `#[serde(with = "exploparse::components")]`:
+///
+/// ```json
+/// {"class": "QB", "nlm": false, "class_number": "46", "segments": [".L744"], "year": "2000"}
+/// ```
+///
+/// Deserializing accepts both forms. The components are validated by parsing
+/// them together, so they have to be written the way `Display` writes them.
+pub mod components {
+    use super::{Components, LCBuf};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(lc: &LCBuf, serializer: S) -> Result<S::Ok, S::Error> {
+        Components::from(&lc.as_lc()).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<LCBuf, D::Error> {
+        LCBuf::deserialize(deserializer)
+    }
+}
@@ -0,0 +1,185 @@
+use crate::{into_report, Cutter, ErrReport, Genre, Note, Prefix, Second, Segment, Year, LC};
+use nom::error::{ErrorKind, ParseError};
+
+/// Builds an `LC` from the text of its components, validating each one the
+/// same way the parser would.
+///
+/// ```
+/// let lc = exploparse::LC::builder()
+///     .class("G")
+///     .class_number("3701")
+///     .cutter(".S12")
+///     .date("1995")
+///     .cutter(".U5")
+///     .build()
+///     .unwrap();
+///
+/// assert_eq!("G 3701 .S12 1995 .U5", lc.to_string());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct LCBuilder<'a> {
+    prefix: Option<&'a str>,
+    class: Option<&'a str>,
+    class_number: Option<&'a str>,
+    segments: Vec<Part<'a>>,
+    year: Option<&'a str>,
+    note: Option<&'a str>,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Part<'a> {
+    Cutter(&'a str),
+    Date(&'a str),
+}
+
+impl<'a> LC<'a> {
+    /// Starts building an `LC` from its components.
+    pub fn builder() -> LCBuilder<'a> {
+        LCBuilder::default()
+    }
+}
+
+impl<'a> LCBuilder<'a> {
+    pub fn new() -> Self {
+        LCBuilder::default()
+    }
+
+    /// Sets the collection prefix, e.g. "Ref". Since it's given explicitly it
+    /// doesn't have to be part of any `Prefixes` vocabulary.
+    pub fn prefix(&mut self, prefix: &'a str) -> &mut Self {
+        self.prefix = Some(prefix);
+        self
+    }
+
+    /// Sets the class letters, e.g. "QC"
+    pub fn class(&mut self, class: &'a str) -> &mut Self {
+        self.class = Some(class);
+        self
+    }
+
+    /// Sets the class number, e.g. "1695.55"
+    pub fn class_number(&mut self, class_number: &'a str) -> &mut Self {
+        self.class_number = Some(class_number);
+        self
+    }
+
+    /// Appends a cutter, e.g. ".C3" or "C3723"
+    pub fn cutter(&mut self, cutter: &'a str) -> &mut Self {
+        self.segments.push(Part::Cutter(cutter));
+        self
+    }
+
+    /// Appends a date that falls between two cutters, e.g. the "1995" of
+    /// "G 3701 .S12 1995 .U5". Use `year` for the date after the last cutter.
+    pub fn date(&mut self, date: &'a str) -> &mut Self {
+        self.segments.push(Part::Date(date));
+        self
+    }
+
+    /// Sets the date following the last cutter, e.g. "2001" or "c2001"
+    pub fn year(&mut self, year: &'a str) -> &mut Self {
+        self.year = Some(year);
+        self
+    }
+
+    /// Sets the trailing note, e.g. "v.2 c.3"
+    pub fn note(&mut self, note: &'a str) -> &mut Self {
+        self.note = Some(note);
+        self
+    }
+
+    /// Validates every component and assembles them into an `LC`, which has
+    /// to parse back into itself from the text it displays as. So a note
+    /// like "A1" that would be read as another cutter is rejected.
+    pub fn build(&self) -> Result<LC<'a>, ErrReport> {
+        let prefix = match self.prefix {
+            Some(prefix) if prefix.is_empty() || prefix.trim() != prefix => {
+                return Err(ErrReport::msg(format!(
+                    "invalid collection prefix {:?}",
+                    prefix
+                )))
+            }
+            prefix => prefix.map(Prefix),
+        };
+
+        let class = self
+            .class
+            .ok_or_else(|| ErrReport::msg("missing class letters"))?;
+        let genre = whole(class, "Genre", Genre::parse_genre)?;
+
+        let class_number = self
+            .class_number
+            .ok_or_else(|| ErrReport::msg("missing class number"))?;
+        let second = whole(class_number, "Second", Second::parse_second)?;
+
+        let mut segments = vec![];
+        for part in &self.segments {
+            match *part {
+                Part::Cutter(cutter) => segments.push(Segment::Cutter(whole(
+                    cutter,
+                    "Cutter",
+                    Cutter::parse_cutter,
+                )?)),
+                Part::Date(_) if segments.is_empty() => {
+                    return Err(ErrReport::msg("a date can't come before the first cutter"))
+                }
+                Part::Date(date) => {
+                    segments.push(Segment::Year(whole(date, "Year", Year::parse_year)?))
+                }
+            }
+        }
+
+        match segments.last() {
+            None => return Err(ErrReport::msg("missing cutter")),
+            Some(Segment::Year(_)) => {
+                return Err(ErrReport::msg(
+                    "a date between cutters has to be followed by a cutter",
+                ))
+            }
+            Some(Segment::Cutter(_)) => (),
+        }
+
+        let year = match self.year {
+            Some(year) => Some(whole(year, "Year", Year::parse_year)?),
+            None => None,
+        };
+
+        let note = match self.note {
+            Some(note) => Some(whole(note, "Note", Note::parse_note)?),
+            None => None,
+        };
+
+        let lc = LC {
+            prefix,
+            nlm: genre.is_nlm(),
+            genre,
+            second,
+            segments,
+            year,
+            note,
+        };
+
+        // Components that are fine on their own can still be read differently
+        // together, which is the same check `LCBuf` makes
+        lc.to_buf()?;
+
+        Ok(lc)
+    }
+}
+
+/// Runs `parser` over all of `i`, reporting anything left over as an error in
+/// `context`.
+fn whole<'a, O>(
+    i: &'a str,
+    context: &'static str,
+    parser: impl Fn(&'a str) -> nom::IResult<&'a str, O, ErrReport>,
+) -> Result<O, ErrReport> {
+    match parser(i) {
+        Ok((rest, output)) if rest.trim().is_empty() => Ok(output),
+        Ok((rest, _)) => {
+            let e = ErrReport::from_error_kind(rest, ErrorKind::Eof);
+            Err(ErrReport::add_context(rest, context, e).with_input(i))
+        }
+        Err(e) => Err(into_report(e).with_input(i)),
+    }
+}
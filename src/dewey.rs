@@ -0,0 +1,195 @@
+use crate::repair::rewrites;
+use crate::{finished, into_report, spanned, strict};
+use crate::{Component, Cutter, ErrReport, Note, Prefix, Prefixes, Repair, Second, Year};
+use nom::bytes::complete::take_while1;
+use nom::character::complete::{char, space0};
+use nom::combinator::{map, opt, verify};
+use nom::error::context;
+use nom::sequence::{pair, preceded, tuple};
+use std::fmt;
+use tracing::instrument;
+
+/// The raw text of each component of a `Dewey`, in the order written.
+type ComponentSpans<'a> = Vec<(Component, &'a str)>;
+
+/// A parsed Dewey Decimal Classification call number, such as "551.5 CLA" or
+/// "813.54 S643w 2001".
+///
+/// Like `LC`, the derived ordering is shelf order: the class number files
+/// numerically and the book number files the same way an LC cutter does.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Dewey<'a> {
+    /// The collection the item is shelved in, such as "Ref" or "Oversize"
+    pub prefix: Option<Prefix<'a>>,
+    /// The class number, always three digits before the decimal point
+    pub class: Second<'a>,
+    /// The book number, either a Cutter-Sanborn number like "C593m" or the
+    /// first letters of the author's name like "CLA"
+    pub cutter: Option<Cutter<'a>>,
+    pub year: Option<Year>,
+    pub note: Option<Note<'a>>,
+}
+
+impl<'a> Dewey<'a> {
+    pub fn maybe_parse(i: &'a str) -> Result<Option<Dewey<'a>>, ErrReport> {
+        Dewey::maybe_parse_with(i, &Prefixes::default())
+    }
+
+    /// Same as `maybe_parse`, recognizing the collection prefixes in
+    /// `prefixes` instead of the default ones.
+    pub fn maybe_parse_with(
+        i: &'a str,
+        prefixes: &Prefixes,
+    ) -> Result<Option<Dewey<'a>>, ErrReport> {
+        if i.is_empty() {
+            Ok(None)
+        } else {
+            let (rest, (dewey, _)) = Dewey::parse_spanned(i, prefixes).map_err(into_report)?;
+            finished(i, rest)?;
+            Ok(Some(dewey))
+        }
+    }
+
+    #[instrument]
+    pub fn parse_dewey(i: &'a str) -> Result<(&'a str, Self), nom::Err<ErrReport>> {
+        let (i, (dewey, _)) = Dewey::parse_spanned(i, &Prefixes::default())?;
+        Ok((i, dewey))
+    }
+
+    /// Parses a call number the same way `LC::parse_repairing_with` does,
+    /// returning the corrections needed to bring it into canonical form.
+    #[instrument]
+    pub fn parse_repairing_with(
+        i: &'a str,
+        prefixes: &Prefixes,
+    ) -> Result<(Self, Vec<Repair>), ErrReport> {
+        let (rest, (dewey, spans)) = Dewey::parse_spanned(i, prefixes).map_err(into_report)?;
+        finished(i, rest)?;
+        let repairs = rewrites(&spans);
+
+        // Like `LC::parse_repairing_with`, nothing is rewritten unreported
+        if repairs.is_empty() {
+            strict::rendering(i.trim(), &dewey).map_err(|e| ErrReport::from(e).with_input(i))?;
+        }
+
+        Ok((dewey, repairs))
+    }
+
+    /// Parses a call number, rejecting anything that isn't already written
+    /// in canonical form, like `LC::parse_strict_with`.
+    #[instrument]
+    pub fn parse_strict_with(i: &'a str, prefixes: &Prefixes) -> Result<Self, ErrReport> {
+        let (rest, (dewey, spans)) = Dewey::parse_spanned(i, prefixes).map_err(into_report)?;
+        finished(i, rest)?;
+        strict::check_dewey(i, &dewey, &rewrites(&spans))
+            .map_err(|e| ErrReport::from(e).with_input(i))?;
+
+        Ok(dewey)
+    }
+
+    fn parse_spanned(
+        i: &'a str,
+        prefixes: &Prefixes,
+    ) -> Result<(&'a str, (Self, ComponentSpans<'a>)), nom::Err<ErrReport>> {
+        Dewey::parse_components(i, prefixes).map_err(|e| match e {
+            nom::Err::Error(e) => nom::Err::Error(e.with_input(i).with_scheme("a Dewey number")),
+            nom::Err::Failure(e) => {
+                nom::Err::Failure(e.with_input(i).with_scheme("a Dewey number"))
+            }
+            nom::Err::Incomplete(needed) => nom::Err::Incomplete(needed),
+        })
+    }
+
+    fn parse_components(
+        i: &'a str,
+        prefixes: &Prefixes,
+    ) -> Result<(&'a str, (Self, ComponentSpans<'a>)), nom::Err<ErrReport>> {
+        let mut spans = vec![];
+
+        let (i, prefix) = match spanned(|i| prefixes.parse_prefix(i))(i) {
+            Ok((rest, prefix)) if rest.starts_with(|c: char| c.is_ascii_digit()) => {
+                (rest, Some(prefix))
+            }
+            _ => (i, None),
+        };
+        spans.extend(prefix.as_ref().map(|(_, span)| (Component::Prefix, *span)));
+
+        let (i, (class, class_span)) = spanned(Dewey::parse_class)(i)?;
+        spans.push((Component::ClassNumber, class_span));
+
+        // A volume designation right after the class number belongs to the
+        // note, not the book number
+        let (i, cutter) = if Note::starts_with_designation(i) {
+            (i, None)
+        } else {
+            opt(spanned(Cutter::parse_cutter))(i)?
+        };
+        spans.extend(cutter.as_ref().map(|(_, span)| (Component::Cutter, *span)));
+
+        let (i, year) = opt(spanned(Year::parse_year))(i)?;
+        spans.extend(year.as_ref().map(|(_, span)| (Component::Year, *span)));
+
+        let (i, note) = opt(spanned(Note::parse_note))(i)?;
+        spans.extend(note.as_ref().map(|(_, span)| (Component::Note, *span)));
+
+        Ok((
+            i,
+            (
+                Self {
+                    prefix: prefix.map(|(prefix, _)| prefix),
+                    class,
+                    cutter: cutter.map(|(cutter, _)| cutter),
+                    year: year.map(|(year, _)| year),
+                    note: note.map(|(note, _)| note),
+                },
+                spans,
+            ),
+        ))
+    }
+
+    /// Dewey classes are three digits from "000" to "999", unlike LC class
+    /// numbers which can be any length, so the class number ends at the
+    /// first space after its digits even when a year follows it directly.
+    fn parse_class(i: &'a str) -> Result<(&'a str, Second<'a>), nom::Err<ErrReport>> {
+        let digits = || take_while1(|c: char| c.is_ascii_digit());
+        let dot = tuple((space0, char('.'), space0));
+
+        context(
+            "Second",
+            map(
+                preceded(
+                    space0,
+                    pair(
+                        verify(digits(), |integer: &str| integer.len() == 3),
+                        opt(preceded(dot, digits())),
+                    ),
+                ),
+                |(integer, fraction)| Second { integer, fraction },
+            ),
+        )(i)
+    }
+}
+
+impl<'a> fmt::Display for Dewey<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(ref prefix) = self.prefix {
+            write!(f, "{} ", prefix)?;
+        }
+
+        write!(f, "{}", self.class)?;
+
+        if let Some(ref cutter) = self.cutter {
+            write!(f, " {}", cutter)?;
+        }
+
+        if let Some(ref year) = self.year {
+            write!(f, " {}", year)?;
+        }
+
+        if let Some(ref note) = self.note {
+            write!(f, " {}", note)?;
+        }
+
+        Ok(())
+    }
+}
@@ -1,6 +1,7 @@
 pub use eyre::*;
 
 use indenter::Indented;
+use serde::Serialize;
 use nom::error::{VerboseErrorKind, ParseError as NomParseError};
 use std::fmt;
 use std::fmt::Write;
@@ -25,6 +26,100 @@ where
     }
 }
 
+impl ErrReport {
+    /// Creates an error report from a plain message, for errors that don't
+    /// originate from parsing.
+    pub fn msg<M>(message: M) -> Self
+    where
+        M: fmt::Display + fmt::Debug + Send + Sync + 'static,
+    {
+        Self {
+            inner: eyre::ErrReport::msg(message),
+        }
+    }
+
+    /// Records the full call number that was being parsed so that nom
+    /// contexts can be located within it.
+    pub(crate) fn with_input(mut self, input: &str) -> Self {
+        self.inner.context_mut().input = Some(input.to_string());
+        self
+    }
+
+    /// Reports `rest`, the input a parser didn't consume, as left over after
+    /// the end of the call number.
+    pub(crate) fn leftover(rest: &str) -> Self {
+        let mut inner: eyre::ErrReport<ExploContext> =
+            eyre::eyre!("unparsed input after the call number");
+        inner.context_mut().nom_errors.push((rest.to_string(), VerboseErrorKind::Context("Trailing")));
+
+        Self { inner }
+    }
+
+    /// Renames the classification scheme a parse error is reported against,
+    /// keeping the nom contexts and input recorded so far.
+    pub(crate) fn with_scheme(mut self, scheme: &str) -> Self {
+        let context = self.inner.context_mut();
+        let nom_errors = std::mem::take(&mut context.nom_errors);
+        let input = context.input.take();
+
+        let mut inner: eyre::ErrReport<ExploContext> =
+            eyre::eyre!("unable to parse fields as {}", scheme);
+        inner.context_mut().nom_errors = nom_errors;
+        inner.context_mut().input = input;
+
+        Self { inner }
+    }
+
+    /// Describes the innermost component that failed to parse, if the error
+    /// came from parsing a call number.
+    pub fn diagnostic(&self) -> Option<Diagnostic> {
+        let context = self.inner.context();
+        let input = context.input.as_ref()?;
+
+        context.nom_errors.iter().find_map(|(remaining, kind)| {
+            let component = match kind {
+                VerboseErrorKind::Context(ctx) => Component::from_context(ctx)?,
+                _ => return None,
+            };
+            let remaining = remaining.trim_start();
+            let offset = input.len().checked_sub(remaining.len())?;
+            let found = remaining.split_whitespace().next().unwrap_or("end of input");
+
+            Some(Diagnostic {
+                component,
+                offset,
+                expected: component.expected().to_string(),
+                found: found.to_string(),
+            })
+        })
+    }
+
+    /// The canonical form violation this error was created from, if it came
+    /// from `LC::parse_strict`.
+    pub fn violation(&self) -> Option<&crate::Violation> {
+        self.inner.downcast_ref()
+    }
+
+    /// A one line summary of why parsing failed, naming the innermost nom
+    /// context and the input remaining when it failed.
+    pub fn reason(&self) -> String {
+        let mut reason = self.inner.to_string();
+        let nom_errors = &self.inner.context().nom_errors;
+        let context = nom_errors.iter().find_map(|(input, kind)| match kind {
+            VerboseErrorKind::Context(context) => Some((context, input)),
+            _ => None,
+        });
+
+        if let Some((context, input)) = context {
+            let _ = write!(reason, " in {} at {:?}", context, input);
+        } else if let Some((input, _)) = nom_errors.first() {
+            let _ = write!(reason, " at {:?}", input);
+        }
+
+        reason
+    }
+}
+
 impl fmt::Debug for ErrReport {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         fmt::Debug::fmt(&self.inner, f)
@@ -35,6 +130,87 @@ pub struct ExploContext {
     backtrace: Backtrace,
     span_trace: SpanTrace,
     nom_errors: Vec<(String, VerboseErrorKind)>,
+    input: Option<String>,
+}
+
+/// The part of a call number that a parse error was found in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Component {
+    Prefix,
+    Genre,
+    ClassNumber,
+    Cutter,
+    Year,
+    Note,
+    /// The class stem of a SuDoc call number, before the colon
+    Stem,
+    /// The book number of a SuDoc call number, after the colon
+    BookNumber,
+    /// Input left over after the end of the call number
+    Trailing,
+}
+
+impl Component {
+    fn from_context(context: &str) -> Option<Self> {
+        match context {
+            "Prefix" => Some(Component::Prefix),
+            "Genre" => Some(Component::Genre),
+            "Second" => Some(Component::ClassNumber),
+            "Cutter" => Some(Component::Cutter),
+            "Year" => Some(Component::Year),
+            "Note" => Some(Component::Note),
+            "Stem" => Some(Component::Stem),
+            "BookNumber" => Some(Component::BookNumber),
+            "Trailing" => Some(Component::Trailing),
+            _ => None,
+        }
+    }
+
+    fn expected(self) -> &'static str {
+        match self {
+            Component::Prefix => "a collection prefix such as \"Ref\"",
+            Component::Genre => "class letters from the LC or NLM outline",
+            Component::ClassNumber => "a class number",
+            Component::Cutter => "a cutter starting with a letter",
+            Component::Year => "a year such as \"2001\", \"c2001\", or \"1990-1991\"",
+            Component::Note => "a trailing note",
+            Component::Stem => "an agency and series like \"I 19.2\"",
+            Component::BookNumber => "letters and digits separated by '.', '/', or '-'",
+            Component::Trailing => "the end of the call number",
+        }
+    }
+}
+
+impl fmt::Display for Component {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Component::Prefix => "collection prefix",
+            Component::Genre => "class letters",
+            Component::ClassNumber => "class number",
+            Component::Cutter => "cutter",
+            Component::Year => "year",
+            Component::Note => "note",
+            Component::Stem => "class stem",
+            Component::BookNumber => "book number",
+            Component::Trailing => "trailing text",
+        };
+
+        f.write_str(name)
+    }
+}
+
+/// A structured description of where and why a call number failed to parse.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Diagnostic {
+    /// The component that failed to parse
+    pub component: Component,
+    /// Byte offset into the original call number where the component failed
+    pub offset: usize,
+    /// A description of what the parser was looking for
+    pub expected: String,
+    /// The text found at `offset`, up to the next space
+    pub found: String,
 }
 
 impl EyreContext for ExploContext {
@@ -43,6 +219,7 @@ impl EyreContext for ExploContext {
             backtrace: Backtrace::capture(),
             span_trace: SpanTrace::capture(),
             nom_errors: Vec::new(),
+            input: None,
         }
     }
 
@@ -97,7 +274,12 @@ impl EyreContext for ExploContext {
 
         for (ind, (i, k)) in self.nom_errors.iter().enumerate() {
             writeln!(f)?;
-            write!(Indented::numbered(f, ind), "{:?} input={:?}", k, i)?;
+            let snippet = self.input.as_ref().and_then(|input| Snippet::new(input, i));
+
+            match snippet {
+                Some(snippet) => write!(Indented::numbered(f, ind), "{:?}\n{}", k, snippet)?,
+                None => write!(Indented::numbered(f, ind), "{:?} input={:?}", k, i)?,
+            }
         }
 
         let span_trace = &self.span_trace;
@@ -118,6 +300,54 @@ impl EyreContext for ExploContext {
     }
 }
 
+/// The original call number with the span that failed to parse underlined,
+/// in the style of rustc's diagnostics:
+///
+/// ```text
+///   |
+///   | Circ. desk
+///   |   ^^^
+/// ```
+struct Snippet<'a> {
+    input: &'a str,
+    column: usize,
+    width: usize,
+}
+
+impl<'a> Snippet<'a> {
+    /// Locates `remaining`, the input left over when a parser failed, within
+    /// the full `input`.
+    fn new(input: &'a str, remaining: &str) -> Option<Self> {
+        let remaining = remaining.trim_start();
+        let offset = input.len().checked_sub(remaining.len())?;
+        let column = input.get(..offset)?.chars().count();
+        let width = remaining
+            .chars()
+            .take_while(|c| !c.is_whitespace())
+            .count()
+            .max(1);
+
+        Some(Self {
+            input,
+            column,
+            width,
+        })
+    }
+}
+
+impl fmt::Display for Snippet<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "  |")?;
+        writeln!(f, "  | {}", self.input)?;
+        write!(
+            f,
+            "  | {}{}",
+            " ".repeat(self.column),
+            "^".repeat(self.width)
+        )
+    }
+}
+
 impl<'a> NomParseError<&'a str> for ErrReport
 {
     fn from_error_kind(input: &'a str, kind: nom::error::ErrorKind) -> Self {
@@ -0,0 +1,200 @@
+use crate::{CallNumber, Dewey, SuDoc, LC};
+
+/// Formats call numbers as spine labels, with each component stacked on its
+/// own line:
+///
+/// ```
+/// use exploparse::{SpineLabel, LC};
+/// use std::convert::TryFrom;
+///
+/// let lc = LC::try_from("QB 46 .L744 2000 v.2 c.3").unwrap();
+/// assert_eq!("QB\n46\n.L744\n2000\nv.2 c.3", SpineLabel::new().format_lc(&lc));
+/// ```
+///
+/// Only the note wraps to fit `width`. The other components are too short
+/// to need it, and breaking one up would change how it reads, so they're
+/// always written whole.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpineLabel {
+    width: usize,
+    wrap: Wrap,
+}
+
+/// How a note longer than the label width is fit onto the label.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Wrap {
+    /// Breaks between words, keeping a designation with its number so that
+    /// "vol. 2" isn't split across lines. Words longer than the width are
+    /// broken wherever they reach it.
+    Words,
+    /// Keeps only the first line that `Words` would write, dropping the
+    /// rest of the note.
+    Truncate,
+}
+
+impl SpineLabel {
+    /// Wraps notes between words at 10 characters.
+    pub fn new() -> Self {
+        SpineLabel::default()
+    }
+
+    /// Sets the maximum number of characters on a line. Zero is treated as
+    /// one so that wrapping always makes progress.
+    pub fn width(&mut self, width: usize) -> &mut Self {
+        self.width = width.max(1);
+        self
+    }
+
+    /// Sets how notes longer than the width are fit onto the label.
+    pub fn wrap(&mut self, wrap: Wrap) -> &mut Self {
+        self.wrap = wrap;
+        self
+    }
+
+    /// The lines of the label for a call number of any scheme.
+    pub fn lines(&self, call_number: &CallNumber<'_>) -> Vec<String> {
+        match call_number {
+            CallNumber::LC(lc) => self.lc_lines(lc),
+            CallNumber::Dewey(dewey) => self.dewey_lines(dewey),
+            CallNumber::SuDoc(sudoc) => self.sudoc_lines(sudoc),
+            CallNumber::Local(local) => self.wrapped(&local.to_string()),
+        }
+    }
+
+    /// The label for a call number of any scheme, one line per component.
+    pub fn format(&self, call_number: &CallNumber<'_>) -> String {
+        self.lines(call_number).join("\n")
+    }
+
+    /// The label for an LC call number, one line per component.
+    pub fn format_lc(&self, lc: &LC<'_>) -> String {
+        self.lc_lines(lc).join("\n")
+    }
+
+    fn lc_lines(&self, lc: &LC<'_>) -> Vec<String> {
+        let mut lines = vec![];
+        lines.extend(lc.prefix.as_ref().map(ToString::to_string));
+        lines.push(lc.genre.as_str().to_string());
+        lines.push(lc.second.to_string());
+        lines.extend(lc.segments.iter().map(ToString::to_string));
+        lines.extend(lc.year.as_ref().map(ToString::to_string));
+
+        if let Some(ref note) = lc.note {
+            lines.extend(self.wrapped(note.as_str()));
+        }
+
+        lines
+    }
+
+    fn dewey_lines(&self, dewey: &Dewey<'_>) -> Vec<String> {
+        let mut lines = vec![];
+        lines.extend(dewey.prefix.as_ref().map(ToString::to_string));
+        lines.push(dewey.class.to_string());
+        lines.extend(dewey.cutter.as_ref().map(ToString::to_string));
+        lines.extend(dewey.year.as_ref().map(ToString::to_string));
+
+        if let Some(ref note) = dewey.note {
+            lines.extend(self.wrapped(note.as_str()));
+        }
+
+        lines
+    }
+
+    /// SuDoc labels put the stem and its colon on the first line and wrap
+    /// the book number below it like a note, since it can run to several
+    /// elements.
+    fn sudoc_lines(&self, sudoc: &SuDoc<'_>) -> Vec<String> {
+        let stem = sudoc
+            .stem
+            .iter()
+            .map(ToString::to_string)
+            .collect::<String>();
+        let mut lines = vec![stem];
+
+        if let Some(ref book) = sudoc.book {
+            lines[0].push(':');
+            let book = book.iter().map(ToString::to_string).collect::<String>();
+            lines.extend(self.wrapped(book.trim_start()));
+        }
+
+        lines
+    }
+
+    fn wrapped(&self, text: &str) -> Vec<String> {
+        match self.wrap {
+            Wrap::Words => wrap_words(text, self.width),
+            Wrap::Truncate => wrap_words(text, self.width).into_iter().take(1).collect(),
+        }
+    }
+}
+
+impl Default for SpineLabel {
+    fn default() -> Self {
+        SpineLabel {
+            width: 10,
+            wrap: Wrap::Words,
+        }
+    }
+}
+
+/// Greedily fills lines of at most `width` characters with the words of
+/// `text`, treating a designation and the number after it as one word.
+fn wrap_words(text: &str, width: usize) -> Vec<String> {
+    let mut lines: Vec<String> = vec![];
+    let mut line = String::new();
+
+    for word in designations(text) {
+        let len = word.chars().count();
+        let line_len = line.chars().count();
+
+        if !line.is_empty() && line_len + 1 + len <= width {
+            line.push(' ');
+            line.push_str(&word);
+            continue;
+        }
+
+        if !line.is_empty() {
+            lines.push(std::mem::take(&mut line));
+        }
+
+        let mut chars = word.chars().peekable();
+        while chars.peek().is_some() {
+            line = chars.by_ref().take(width).collect();
+            if chars.peek().is_some() {
+                lines.push(std::mem::take(&mut line));
+            }
+        }
+    }
+
+    if !line.is_empty() {
+        lines.push(line);
+    }
+
+    lines
+}
+
+/// Splits `text` into words, joining a word of letters, like "vol." or
+/// "copy", to the number that follows it.
+fn designations(text: &str) -> Vec<String> {
+    let mut words: Vec<String> = vec![];
+    let mut keyword = false;
+
+    for word in text.split_whitespace() {
+        match words.last_mut() {
+            Some(last) if keyword && word.starts_with(|c: char| c.is_ascii_digit()) => {
+                last.push(' ');
+                last.push_str(word);
+                keyword = false;
+            }
+            _ => {
+                keyword = word
+                    .trim_end_matches('.')
+                    .chars()
+                    .all(|c| c.is_alphabetic());
+                words.push(word.to_string());
+            }
+        }
+    }
+
+    words
+}
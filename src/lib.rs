@@ -1,89 +1,274 @@
-#![feature(backtrace, error_iter)]
-
 use nom::bytes::complete::{is_a, take, take_while, take_while_m_n};
 use nom::character::complete::anychar;
-use nom::combinator::{map, map_res, opt, verify};
+use nom::character::complete::{char, one_of};
+use nom::combinator::{cond, map, map_res, opt, verify};
+use nom::sequence::{pair, preceded};
 use nom::error::context;
 use tracing::{span, Level, instrument};
-use nom::Err;
-pub use error::ErrReport;
+pub use buf::{components, LCBuf};
+pub use builder::LCBuilder;
+pub use dewey::Dewey;
+pub use error::{Component, Diagnostic, ErrReport};
+pub use label::{SpineLabel, Wrap};
+pub use local::{Local, Locals};
+pub use note::{Note, NotePart};
+pub use parser::Parser;
+pub use prefix::{Prefix, Prefixes};
+pub use repair::Repair;
+pub use strict::Violation;
+pub use sudoc::{Element, Piece, SuDoc};
+use repair::Spans;
 use nom::error::ParseError;
+use std::cmp::Ordering;
+use std::fmt;
 
+mod buf;
+mod builder;
+mod dewey;
 mod error;
+mod label;
+mod local;
+mod note;
+mod outline;
+mod parser;
+mod prefix;
+mod repair;
+mod strict;
+mod sudoc;
 
-#[derive(Debug, PartialEq)]
+/// The class letters of an LC or NLM call number, kept as written. They're
+/// matched against the outline ignoring case, and file that way too.
+#[derive(Debug, PartialEq, Eq, Hash)]
 pub struct Genre<'a>(&'a str);
 
-#[derive(Debug, PartialEq)]
-pub struct Second(f64);
+/// A class number, kept as the digits that were written on either side of
+/// the decimal point.
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct Second<'a> {
+    integer: &'a str,
+    fraction: Option<&'a str>,
+}
 
-#[derive(Debug, PartialEq)]
-pub struct Third<'a> {
+/// A cutter such as ".C3723" or "H26x": one or more letters, digits that file
+/// as a decimal fraction, and an optional work mark or second cutter run
+/// together with the first.
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct Cutter<'a> {
     has_dot: bool,
-    body: &'a str,
+    letters: &'a str,
+    digits: &'a str,
+    suffix: Option<&'a str>,
 }
 
-#[derive(Debug, PartialEq)]
+/// The date of a call number, such as "2001", "1990-1991", "[2005]",
+/// "c2001", "19--", or "2002a".
+///
+/// The derived ordering is chronological: fields are declared in the order
+/// they are filed, so an uncertain decade like "19--" files before the years
+/// within it, and a single year files before a range starting that year.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Year {
+    /// The first year covered, e.g. 1900 for "19--"
     year: u16,
+    /// How many leading digits were written, 2 for "19--" and 4 for "1990"
+    known: u8,
+    /// The last year of a range like "1990-1991"
+    end: Option<u16>,
+    /// Whether the year starts a range that hasn't ended, e.g. "1984-"
+    open: bool,
+    /// A work letter distinguishing works from the same year, e.g. "2002a"
     suffix: Option<char>,
+    /// Whether the year was bracketed to show it was inferred, e.g. "[2005]"
+    inferred: bool,
+    /// Whether the year is a copyright date, e.g. "c2001"
+    copyright: bool,
 }
 
-#[derive(Debug, PartialEq)]
-pub struct Note<'a>(&'a str);
+/// One piece of the cutter section of a call number. Map and atlas numbers
+/// can have a date between cutters, e.g. "G 3701 .S12 1995 .U5".
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Segment<'a> {
+    Cutter(Cutter<'a>),
+    Year(Year),
+}
 
-#[derive(Debug, PartialEq)]
+/// A parsed Library of Congress call number.
+///
+/// The derived ordering is shelf order: fields are declared in the order they
+/// are filed, and a missing cutter, year, or note files before a present one.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct LC<'a> {
+    /// The collection the item is shelved in, such as "Ref" or "Oversize".
+    /// Items without one file before any that have one.
+    pub prefix: Option<Prefix<'a>>,
+    /// Whether the class letters are from the National Library of Medicine
+    /// schedules rather than the LC outline. NLM call numbers file after LC
+    /// ones since they're shelved as a separate collection.
+    pub nlm: bool,
     pub genre: Genre<'a>,
-    pub second: Second,
-    pub third: Third<'a>,
-    pub fourth: Option<Third<'a>>,
+    pub second: Second<'a>,
+    /// Cutters in the order written, with any dates that fall between them.
+    /// Always starts with a cutter.
+    pub segments: Vec<Segment<'a>>,
+    /// The date following the last cutter
     pub year: Option<Year>,
     pub note: Option<Note<'a>>, // Note bits at the end
 }
 
-use std::fmt;
+impl<'a> PartialOrd for Genre<'a> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<'a> Ord for Genre<'a> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0
+            .to_ascii_uppercase()
+            .cmp(&other.0.to_ascii_uppercase())
+            .then_with(|| self.0.cmp(other.0))
+    }
+}
+
+impl<'a> PartialOrd for Second<'a> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<'a> Ord for Second<'a> {
+    /// Class numbers compare numerically, so "21.5" and "21.50" file together
+    /// and only fall back to their written form to break the tie.
+    fn cmp(&self, other: &Self) -> Ordering {
+        let integer = |second: &Self| second.integer.trim_start_matches('0');
+        let fraction = |second: &Self| second.fraction.unwrap_or("").trim_end_matches('0');
+
+        integer(self)
+            .len()
+            .cmp(&integer(other).len())
+            .then_with(|| integer(self).cmp(integer(other)))
+            .then_with(|| fraction(self).cmp(fraction(other)))
+            .then_with(|| self.integer.cmp(other.integer))
+            .then_with(|| self.fraction.cmp(&other.fraction))
+    }
+}
+
+impl<'a> fmt::Display for Second<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.integer)?;
+
+        if let Some(fraction) = self.fraction {
+            write!(f, ".{}", fraction)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a> PartialOrd for Cutter<'a> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<'a> Ord for Cutter<'a> {
+    /// Cutters file by their letters and then by their digits as a decimal
+    /// fraction, so ".C43" files before ".C5" and ".C5" alongside ".C50".
+    fn cmp(&self, other: &Self) -> Ordering {
+        let letters = |cutter: &Self| cutter.letters.chars().map(|c| c.to_ascii_uppercase()).collect::<String>();
+        let digits = |cutter: &Self| cutter.digits.trim_end_matches('0');
+        let suffix = |cutter: &Self| cutter.suffix.map(str::to_ascii_uppercase);
+
+        letters(self)
+            .cmp(&letters(other))
+            .then_with(|| digits(self).cmp(digits(other)))
+            .then_with(|| suffix(self).cmp(&suffix(other)))
+            .then_with(|| self.letters.cmp(other.letters))
+            .then_with(|| self.digits.cmp(other.digits))
+            .then_with(|| self.suffix.cmp(&other.suffix))
+            .then_with(|| self.has_dot.cmp(&other.has_dot))
+    }
+}
+
 impl<'a> fmt::Display for LC<'a> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.genre.0)?;
-        write!(f, " {}", self.second.0)?;
+        if let Some(ref prefix) = self.prefix {
+            write!(f, "{} ", prefix)?;
+        }
 
-        write!(f, " ")?;
-        write!(f, "{}", self.third)?;
+        write!(f, "{}", self.genre.0)?;
+        write!(f, " {}", self.second)?;
 
-        if let Some(ref fourth) = self.fourth {
+        for segment in &self.segments {
             write!(f, " ")?;
-            write!(f, "{}", fourth)?;
+            write!(f, "{}", segment)?;
         }
 
-        if let Some(Year {
-            ref year,
-            ref suffix,
-        }) = self.year
-        {
+        if let Some(ref year) = self.year {
             write!(f, " ")?;
             write!(f, "{}", year)?;
-
-            if let Some(ref suffix) = suffix {
-                write!(f, "{}", suffix)?;
-            }
         }
 
         if let Some(ref note) = self.note {
             write!(f, " ")?;
-            write!(f, "{}", note.0)?;
+            write!(f, "{}", note)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a> fmt::Display for Segment<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Segment::Cutter(cutter) => write!(f, "{}", cutter),
+            Segment::Year(year) => write!(f, "{}", year),
+        }
+    }
+}
+
+impl fmt::Display for Year {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.inferred {
+            write!(f, "[")?;
+        }
+
+        if self.copyright {
+            write!(f, "c")?;
+        }
+
+        let known = usize::from(self.known);
+        let digits = format!("{:04}", self.year);
+        write!(f, "{}{}", &digits[..known], &"----"[known..])?;
+
+        if let Some(end) = self.end {
+            write!(f, "-{:04}", end)?;
+        } else if self.open {
+            write!(f, "-")?;
+        }
+
+        if let Some(ref suffix) = self.suffix {
+            write!(f, "{}", suffix)?;
+        }
+
+        if self.inferred {
+            write!(f, "]")?;
         }
 
         Ok(())
     }
 }
 
-impl<'a> fmt::Display for Third<'a> {
+impl<'a> fmt::Display for Cutter<'a> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         if self.has_dot {
             write!(f, ".")?;
         }
-        write!(f, "{}", self.body)?;
+        write!(f, "{}{}", self.letters, self.digits)?;
+
+        if let Some(suffix) = self.suffix {
+            write!(f, "{}", suffix)?;
+        }
 
         Ok(())
     }
@@ -94,18 +279,47 @@ impl<'a> Genre<'a> {
     fn parse_genre(i: &'a str) -> Result<(&'a str, Self), nom::Err<ErrReport>> {
         context(
             "Genre",
-            map(take_while_m_n(1, 2, nom::AsChar::is_alpha), Genre),
+            map(
+                verify(take_while_m_n(1, 3, nom::AsChar::is_alpha), |class: &str| {
+                    outline::canonical(class).is_some()
+                }),
+                Genre,
+            ),
         )(i)
     }
+
+    /// The caption of this class in the LC outline, e.g. "Physics" for "QC",
+    /// or in the NLM schedules, e.g. "Practice of Medicine" for "WB".
+    pub fn caption(&self) -> &'static str {
+        let class = self.canonical();
+        outline::caption(class)
+            .or_else(|| outline::nlm_caption(class))
+            .expect("genres are validated against the outline when parsed")
+    }
+
+    /// Whether this class is one of the NLM schedules, QS through QZ and W.
+    pub fn is_nlm(&self) -> bool {
+        outline::nlm_caption(self.canonical()).is_some()
+    }
+
+    /// The class letters as the outline spells them, e.g. "QC" for "qc"
+    fn canonical(&self) -> &'static str {
+        outline::canonical(self.0).expect("genres are validated against the outline when parsed")
+    }
+
+    /// The class letters, e.g. "QC"
+    pub fn as_str(&self) -> &'a str {
+        self.0
+    }
 }
 
-impl Second {
+impl<'a> Second<'a> {
     #[instrument]
-    fn parse_second(i: &str) -> Result<(&str, Self), nom::Err<ErrReport>> {
+    fn parse_second(i: &'a str) -> Result<(&'a str, Self), nom::Err<ErrReport>> {
         let mut seen_dot = false;
         let mut prev = None;
         let mut end = None;
-        for (ind, c) in i.chars().enumerate() {
+        for (ind, c) in i.char_indices() {
             match c {
                 '.' => {
                     if seen_dot {
@@ -115,7 +329,7 @@ impl Second {
                         seen_dot = true;
                     }
                 }
-                'A'..='Z' => {
+                c if c.is_alphabetic() => {
                     if prev == Some('.') {
                         end = Some(ind - 1);
                         break;
@@ -124,45 +338,121 @@ impl Second {
                         break;
                     }
                 }
+                // Spaces only belong to the class number next to its decimal
+                // point, as in "1695 .55", so that "7820 1985" stops at "7820"
+                c if c.is_whitespace() => {
+                    let next = i[ind..].trim_start().chars().next();
+                    let beside_dot = prev == Some('.') || (!seen_dot && next == Some('.'));
+                    if prev.is_some() && !beside_dot {
+                        end = Some(ind);
+                        break;
+                    }
+                    continue;
+                }
                 _ => (),
             }
             prev = Some(c);
         }
 
-        let end = end.unwrap_or_else(|| i.len());
+        let end = end.unwrap_or(i.len());
 
         let after = &i[end..];
-        let second = &i[..end];
-        let input = second
-            .replace(" ", "");
+        let second = i[..end].trim();
 
-        let span = span!(Level::ERROR, "Second::parse_float", ?input);
+        let span = span!(Level::ERROR, "Second::parse_digits", ?second);
         let _guard = span.enter();
 
-        let second = input.parse()
-            .map_err(ErrReport::from)
-            .map_err(nom::Err::Error)?;
+        // Spaces around the decimal point are dropped but the digits on either
+        // side of it are kept exactly as written
+        let (integer, fraction) = match second.find('.') {
+            Some(dot) => (
+                second[..dot].trim_end(),
+                Some(second[dot + 1..].trim_start()).filter(|fraction| !fraction.is_empty()),
+            ),
+            None => (second, None),
+        };
+
+        let is_digits = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+        if !is_digits(integer) || !fraction.map(is_digits).unwrap_or(true) {
+            let e = ErrReport::from_error_kind(i, nom::error::ErrorKind::Digit);
+            return Err(nom::Err::Error(ErrReport::add_context(i, "Second", e)));
+        }
+
+        Ok((after, Second { integer, fraction }))
+    }
+
+    /// The digits before the decimal point, e.g. "1695" for "1695.55"
+    pub fn integer(&self) -> &'a str {
+        self.integer
+    }
 
-        Ok((after, Second(second)))
+    /// The digits after the decimal point, e.g. "55" for "1695.55"
+    pub fn fraction(&self) -> Option<&'a str> {
+        self.fraction
     }
 }
 
-impl<'a> Third<'a> {
+impl<'a> Cutter<'a> {
     #[instrument]
-    fn parse_third(i: &'a str) -> Result<(&'a str, Self), nom::Err<ErrReport>> {
+    fn parse_cutter(i: &'a str) -> Result<(&'a str, Self), nom::Err<ErrReport>> {
         let (i, _) = opt(is_a(" "))(i)?;
         let (i, has_dot) = map(opt(nom::character::complete::char('.')), |dot| {
             dot.is_some()
         })(i)?;
         let (i, _) = opt(is_a(" "))(i)?;
         let (i, body) = context(
-            "Third",
+            "Cutter",
             verify(take_while(|c: char| c.is_alphanumeric()), |s: &str| {
                 s.chars().next().map(|c| c.is_alphabetic()).unwrap_or(false)
             }),
         )(i)?;
 
-        Ok((i, Third { has_dot, body }))
+        let split = |s: &'a str, pred: fn(char) -> bool| {
+            let end = s.find(|c: char| !pred(c)).unwrap_or(s.len());
+            s.split_at(end)
+        };
+        let (letters, rest) = split(body, char::is_alphabetic);
+        let (digits, suffix) = split(rest, |c| c.is_ascii_digit());
+        let suffix = Some(suffix).filter(|suffix| !suffix.is_empty());
+
+        Ok((
+            i,
+            Cutter {
+                has_dot,
+                letters,
+                digits,
+                suffix,
+            },
+        ))
+    }
+
+    /// Parses a cutter that must be a single capital letter followed by
+    /// digits, like "U5" but not "vol".
+    fn parse_shaped_cutter(i: &'a str) -> Result<(&'a str, Self), nom::Err<ErrReport>> {
+        verify(Cutter::parse_cutter, |cutter: &Cutter<'_>| {
+            let mut letters = cutter.letters.chars();
+            let capital = letters.next().map(|c| c.is_ascii_uppercase()).unwrap_or(false);
+            capital && letters.next().is_none() && !cutter.digits.is_empty()
+        })(i)
+    }
+
+    pub fn has_dot(&self) -> bool {
+        self.has_dot
+    }
+
+    /// The leading letters, e.g. "C" for ".C3723"
+    pub fn letters(&self) -> &'a str {
+        self.letters
+    }
+
+    /// The digits following the letters, which file as a decimal fraction
+    pub fn digits(&self) -> &'a str {
+        self.digits
+    }
+
+    /// Anything after the digits, e.g. the work mark "x" of "H26x"
+    pub fn suffix(&self) -> Option<&'a str> {
+        self.suffix
     }
 }
 
@@ -170,63 +460,493 @@ impl Year {
     #[instrument]
     fn parse_year(i: &str) -> Result<(&str, Self), nom::Err<ErrReport>> {
         let (i, _) = opt(is_a(" "))(i)?;
-        let (i, year) = map_res(take(4usize), str::parse)(i)?;
+        context("Year", Year::parse_date)(i)
+    }
+
+    fn parse_date(i: &str) -> Result<(&str, Self), nom::Err<ErrReport>> {
+        let (i, inferred) = map(opt(char('[')), |bracket| bracket.is_some())(i)?;
+        let (i, copyright) = map(opt(char('c')), |mark| mark.is_some())(i)?;
+
+        // Unknown trailing digits are written as dashes, e.g. "199-" or "19--"
+        let (i, digits) = verify(take(4usize), |digits: &str| {
+            let known = digits.trim_end_matches('-');
+            known.len() >= 2 && known.bytes().all(|b| b.is_ascii_digit())
+        })(i)?;
+        let known = digits.trim_end_matches('-');
+        let scale = 10u16.pow(4 - known.len() as u32);
+        let year = known.parse::<u16>().expect("verified to be digits") * scale;
+
+        // Only a fully known year can start a range, written "1990-1991" or
+        // "1990/91", with the end abbreviated to the digits that changed
+        let (i, end) = if scale == 1 {
+            let end_digits = verify(
+                take_while_m_n(2, 4, |c: char| c.is_ascii_digit()),
+                |end: &str| end.len() == 2 || end.len() == 4,
+            );
+            opt(preceded(one_of("-/"), map_res(end_digits, str::parse::<u16>)))(i)?
+        } else {
+            (i, None)
+        };
+        let end = match end {
+            Some(end) => match range_end(year, end) {
+                Some(end) => Some(end),
+                None => {
+                    let e = ErrReport::from_error_kind(i, nom::error::ErrorKind::Verify);
+                    return Err(nom::Err::Error(e));
+                }
+            },
+            None => None,
+        };
+
+        // A range that hasn't ended yet, like "1984-", ends with its dash
+        let open = scale == 1
+            && end.is_none()
+            && i.starts_with('-')
+            && i[1..].chars().next().is_none_or(|c| c.is_whitespace() || c == ']');
+        let i = if open { &i[1..] } else { i };
+
         let (i, suffix) = opt(verify(anychar, |c| c.is_alphabetic()))(i)?;
+        let (i, _) = cond(inferred, char(']'))(i)?;
+
+        Ok((
+            i,
+            Year {
+                year,
+                known: known.len() as u8,
+                end,
+                open,
+                suffix,
+                inferred,
+                copyright,
+            },
+        ))
+    }
 
-        Ok((i, Year { year, suffix }))
+    /// The first year covered, e.g. 1990 for both "1990" and "199-"
+    pub fn first(&self) -> u16 {
+        self.year
     }
-}
 
-impl<'a> Note<'a> {
-    // Implement note pieces here. Read whole string at the end and hold data
-    #[instrument]
-    fn parse_note(i: &'a str) -> Result<(&'a str, Self), nom::Err<ErrReport>> {
-        let i = i.trim();
-        if i.is_empty() {
-            return Err(Err::Error(ErrReport::from_error_kind(i, nom::error::ErrorKind::Eof).into()));
-        } else {
-            let note = Note(i);
-            Ok((i, note))
+    /// The last year covered, e.g. 1991 for "1990-1991" or 1999 for "19--".
+    /// A range that hasn't ended, like "1984-", covers every year after its
+    /// first, so this is `u16::MAX`.
+    pub fn last(&self) -> u16 {
+        if self.open {
+            return u16::MAX;
         }
+
+        let scale = 10u16.pow(4 - u32::from(self.known));
+        self.end.unwrap_or(self.year + scale - 1)
+    }
+
+    /// Whether the year starts a range that hasn't ended, as in "1984-"
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    /// Whether some digits of the year are unknown, as in "19--"
+    pub fn is_uncertain(&self) -> bool {
+        self.known < 4
+    }
+
+    /// Whether the year was bracketed to show it was inferred, as in "[2005]"
+    pub fn is_inferred(&self) -> bool {
+        self.inferred
+    }
+
+    /// Whether the year is a copyright date, as in "c2001"
+    pub fn is_copyright(&self) -> bool {
+        self.copyright
+    }
+
+    /// The work letter following the year, as in "2002a"
+    pub fn suffix(&self) -> Option<char> {
+        self.suffix
     }
 }
 
 impl<'a> LC<'a> {
     pub fn maybe_parse(i: &'a str) -> Result<Option<LC<'a>>, ErrReport> {
+        LC::maybe_parse_with(i, &Prefixes::default())
+    }
+
+    /// Same as `maybe_parse`, recognizing the collection prefixes in
+    /// `prefixes` instead of the default ones.
+    pub fn maybe_parse_with(i: &'a str, prefixes: &Prefixes) -> Result<Option<LC<'a>>, ErrReport> {
         if i.is_empty() {
             Ok(None)
         } else {
             // Shows fixed LC otherwise
-            let (_, lc) = LC::parse_lc(i)
-                .map_err(|e| match e {
-                    nom::Err::Error(e) | nom::Err::Failure(e) => e,
-                    _ => unreachable!(),
-                })?;
+            let (rest, (lc, _)) = LC::parse_spanned(i, prefixes).map_err(into_report)?;
+            finished(i, rest)?;
             Ok(Some(lc))
         }
     }
 
     #[instrument]
     pub fn parse_lc(i: &'a str) -> Result<(&'a str, Self), nom::Err<ErrReport>> {
-        let (i, genre) = Genre::parse_genre(i)?;
-        let (i, second) = Second::parse_second(i)?;
-        let (i, third) = Third::parse_third(i)?;
-        let (i, fourth) = opt(Third::parse_third)(i)?;
-        let (i, year) = opt(Year::parse_year)(i)?;
-        let (i, note) = opt(Note::parse_note)(i)?;
+        let (i, (lc, _)) = LC::parse_spanned(i, &Prefixes::default())?;
+        Ok((i, lc))
+    }
+
+    /// Parses a call number the same way `parse_lc` does, also returning the
+    /// corrections needed to bring the input into canonical form. The
+    /// returned `LC` has those corrections applied.
+    #[instrument]
+    pub fn parse_repairing(i: &'a str) -> Result<(Self, Vec<Repair>), ErrReport> {
+        LC::parse_repairing_with(i, &Prefixes::default())
+    }
+
+    /// Same as `parse_repairing`, recognizing the collection prefixes in
+    /// `prefixes` instead of the default ones.
+    #[instrument]
+    pub fn parse_repairing_with(i: &'a str, prefixes: &Prefixes) -> Result<(Self, Vec<Repair>), ErrReport> {
+        let (rest, (mut lc, spans)) = LC::parse_spanned(i, prefixes).map_err(into_report)?;
+        finished(i, rest)?;
+        let repairs = spans.repairs();
+
+        if repairs.contains(&Repair::UppercasedClass) {
+            lc.genre = Genre(lc.genre.canonical());
+        }
+
+        if repairs.contains(&Repair::InsertedDot) {
+            if let Some(Segment::Cutter(ref mut cutter)) = lc.segments.first_mut() {
+                cutter.has_dot = true;
+            }
+        }
+
+        // Rewriting the input in a way no repair names would change the row
+        // without flagging it, so that's rejected instead
+        if repairs.is_empty() {
+            strict::rendering(i.trim(), &lc).map_err(|e| ErrReport::from(e).with_input(i))?;
+        }
+
+        Ok((lc, repairs))
+    }
+
+    /// Parses a call number, rejecting anything that isn't already written
+    /// in canonical form. The returned error wraps a `Violation` describing
+    /// the first deviation found, see `ErrReport::violation`.
+    #[instrument]
+    pub fn parse_strict(i: &'a str) -> Result<Self, ErrReport> {
+        LC::parse_strict_with(i, &Prefixes::default())
+    }
+
+    /// Same as `parse_strict`, recognizing the collection prefixes in
+    /// `prefixes` instead of the default ones.
+    #[instrument]
+    pub fn parse_strict_with(i: &'a str, prefixes: &Prefixes) -> Result<Self, ErrReport> {
+        let (rest, (lc, spans)) = LC::parse_spanned(i, prefixes).map_err(into_report)?;
+        finished(i, rest)?;
+        strict::check(i, &lc, &spans).map_err(|e| ErrReport::from(e).with_input(i))?;
+
+        Ok(lc)
+    }
+
+    fn parse_spanned(
+        i: &'a str,
+        prefixes: &Prefixes,
+    ) -> Result<(&'a str, (Self, Spans<'a>)), nom::Err<ErrReport>> {
+        // Errors in call numbers with NLM class letters name that scheme so
+        // they aren't reported as malformed LC call numbers
+        let report = |e: ErrReport| {
+            let class = prefixes.parse_prefix(i).map(|(rest, _)| rest).unwrap_or(i);
+            match Genre::parse_genre(class) {
+                Ok((_, genre)) if genre.is_nlm() => e.with_input(i).with_scheme("an NLM number"),
+                _ => e.with_input(i),
+            }
+        };
+
+        LC::parse_components(i, prefixes).map_err(|e| match e {
+            nom::Err::Error(e) => nom::Err::Error(report(e)),
+            nom::Err::Failure(e) => nom::Err::Failure(report(e)),
+            nom::Err::Incomplete(needed) => nom::Err::Incomplete(needed),
+        })
+    }
+
+    fn parse_components(
+        i: &'a str,
+        prefixes: &Prefixes,
+    ) -> Result<(&'a str, (Self, Spans<'a>)), nom::Err<ErrReport>> {
+        // A prefix only counts if class letters follow it, so that a prefix
+        // that doubles as a class like "CD" still parses as the class
+        let (i, prefix) = match spanned(|i| prefixes.parse_prefix(i))(i) {
+            Ok((rest, prefix)) if Genre::parse_genre(rest).is_ok() => (rest, Some(prefix)),
+            _ => (i, None),
+        };
+        let (i, (genre, genre_span)) = spanned(Genre::parse_genre)(i)?;
+        let (i, (second, second_span)) = spanned(Second::parse_second)(i)?;
+        let (mut i, (first, first_span)) = spanned(Cutter::parse_cutter)(i)?;
+        let mut segments = vec![Segment::Cutter(first)];
+        let mut segment_spans = vec![(Component::Cutter, first_span)];
+
+        // The second cutter is accepted as loosely as the first, but anything
+        // past it or after a date has to look like a cutter so that words of a
+        // trailing note like "vol. tg" aren't mistaken for more cutters
+        loop {
+            if Note::starts_with_designation(i) {
+                break;
+            }
+
+            let parse_cutter = if segments.len() == 1 {
+                Cutter::parse_cutter
+            } else {
+                Cutter::parse_shaped_cutter
+            };
+
+            // "c2001" would otherwise pass for a lowercase cutter
+            let copyright = matches!(Year::parse_year(i), Ok((_, year)) if year.copyright);
+            let (rest, cutter) = if copyright {
+                (i, None)
+            } else {
+                opt(spanned(parse_cutter))(i)?
+            };
+            if let Some((cutter, span)) = cutter {
+                segments.push(Segment::Cutter(cutter));
+                segment_spans.push((Component::Cutter, span));
+                i = rest;
+                continue;
+            }
+
+            // A date only belongs among the segments if another cutter follows
+            // it, otherwise it's the trailing year
+            let dated = pair(spanned(Year::parse_year), spanned(Cutter::parse_shaped_cutter));
+            let (rest, dated) = opt(dated)(i)?;
+            if let Some(((year, year_span), (cutter, cutter_span))) = dated {
+                segments.push(Segment::Year(year));
+                segments.push(Segment::Cutter(cutter));
+                segment_spans.push((Component::Year, year_span));
+                segment_spans.push((Component::Cutter, cutter_span));
+                i = rest;
+                continue;
+            }
+
+            break;
+        }
+
+        let (i, year) = opt(spanned(Year::parse_year))(i)?;
+        let (i, note) = opt(spanned(Note::parse_note))(i)?;
+
+        let spans = Spans {
+            prefix: prefix.as_ref().map(|(_, span)| *span),
+            genre: genre_span,
+            second: second_span,
+            segments: segment_spans,
+            year: year.as_ref().map(|(_, span)| *span),
+            note: note.as_ref().map(|(_, span)| *span),
+        };
 
         Ok((
             i,
-            Self {
-                genre,
-                second,
-                third,
-                fourth,
-                year,
-                note,
-            },
+            (
+                Self {
+                    prefix: prefix.map(|(prefix, _)| prefix),
+                    nlm: genre.is_nlm(),
+                    genre,
+                    second,
+                    segments,
+                    year: year.map(|(year, _)| year),
+                    note: note.map(|(note, _)| note),
+                },
+                spans,
+            ),
         ))
     }
+
+    /// The cutters of this call number, skipping any dates between them.
+    pub fn cutters(&self) -> impl Iterator<Item = &Cutter<'a>> {
+        self.segments.iter().filter_map(|segment| match segment {
+            Segment::Cutter(cutter) => Some(cutter),
+            Segment::Year(_) => None,
+        })
+    }
+}
+
+/// A call number in any of the classification schemes this crate parses,
+/// detected from how the call number is written.
+///
+/// The derived ordering shelves every LC call number before any Dewey one,
+/// and those before any SuDoc one, so that mixed collections sort into one
+/// run per scheme. Local shelving locations file after everything that's
+/// classified.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum CallNumber<'a> {
+    LC(LC<'a>),
+    Dewey(Dewey<'a>),
+    SuDoc(SuDoc<'a>),
+    Local(Local),
+}
+
+impl<'a> CallNumber<'a> {
+    pub fn maybe_parse(i: &'a str) -> Result<Option<CallNumber<'a>>, ErrReport> {
+        CallNumber::maybe_parse_with(i, &Prefixes::default(), &Locals::default())
+    }
+
+    /// Same as `maybe_parse`, recognizing the collection prefixes in
+    /// `prefixes` and the local shelving locations in `locals` instead of
+    /// the default ones.
+    pub fn maybe_parse_with(
+        i: &'a str,
+        prefixes: &Prefixes,
+        locals: &Locals,
+    ) -> Result<Option<CallNumber<'a>>, ErrReport> {
+        if let Some((local, _)) = locals.parse_local(i) {
+            return Ok(Some(CallNumber::Local(local)));
+        }
+
+        CallNumber::detect(
+            i,
+            prefixes,
+            |i| LC::maybe_parse_with(i, prefixes).map(|lc| lc.map(CallNumber::LC)),
+            |i| Dewey::maybe_parse_with(i, prefixes).map(|dewey| dewey.map(CallNumber::Dewey)),
+            |i| SuDoc::maybe_parse(i).map(|sudoc| sudoc.map(CallNumber::SuDoc)),
+        )
+    }
+
+    /// Parses a call number of any scheme, also returning the corrections
+    /// needed to bring it into canonical form, see `LC::parse_repairing`.
+    pub fn parse_repairing_with(
+        i: &'a str,
+        prefixes: &Prefixes,
+        locals: &Locals,
+    ) -> Result<(Self, Vec<Repair>), ErrReport> {
+        if let Some((local, canonical)) = locals.parse_local(i) {
+            let repairs = if canonical { vec![] } else { vec![Repair::NormalizedLocal] };
+            return Ok((CallNumber::Local(local), repairs));
+        }
+
+        CallNumber::detect(
+            i,
+            prefixes,
+            |i| LC::parse_repairing_with(i, prefixes).map(|(lc, repairs)| (CallNumber::LC(lc), repairs)),
+            |i| {
+                Dewey::parse_repairing_with(i, prefixes)
+                    .map(|(dewey, repairs)| (CallNumber::Dewey(dewey), repairs))
+            },
+            |i| SuDoc::parse_repairing(i).map(|(sudoc, repairs)| (CallNumber::SuDoc(sudoc), repairs)),
+        )
+    }
+
+    /// Parses a call number of any scheme, rejecting anything that isn't
+    /// already written in canonical form, see `LC::parse_strict`.
+    pub fn parse_strict_with(i: &'a str, prefixes: &Prefixes, locals: &Locals) -> Result<Self, ErrReport> {
+        match locals.parse_local(i) {
+            Some((local, true)) => return Ok(CallNumber::Local(local)),
+            Some((_, false)) => return Err(ErrReport::from(Violation::NonCanonicalLocal).with_input(i)),
+            None => (),
+        }
+
+        CallNumber::detect(
+            i,
+            prefixes,
+            |i| LC::parse_strict_with(i, prefixes).map(CallNumber::LC),
+            |i| Dewey::parse_strict_with(i, prefixes).map(CallNumber::Dewey),
+            |i| SuDoc::parse_strict(i).map(CallNumber::SuDoc),
+        )
+    }
+
+    /// Only SuDoc call numbers have a colon, so those go straight to `sudoc`.
+    /// Otherwise tries `lc` first, falling back to `dewey` when that fails
+    /// and the class starts with a digit. Trying LC first keeps prefixes that
+    /// double as LC classes, like "CD 921 .A1", from being read as Dewey.
+    /// A bare SuDoc stem like "I 19.2" is tried last, but only when no LC or
+    /// NLM class starts with its first letter, so that call numbers with an
+    /// unknown class like "QQ 5.D5" or a strict violation like "QC 5.D5"
+    /// keep their LC error.
+    fn detect<T>(
+        i: &'a str,
+        prefixes: &Prefixes,
+        lc: impl Fn(&'a str) -> Result<T, ErrReport>,
+        dewey: impl Fn(&'a str) -> Result<T, ErrReport>,
+        sudoc: impl Fn(&'a str) -> Result<T, ErrReport>,
+    ) -> Result<T, ErrReport> {
+        if i.contains(':') {
+            return sudoc(i);
+        }
+
+        let class = match prefixes.parse_prefix(i) {
+            Ok((rest, _)) => rest,
+            Err(_) => i.trim_start(),
+        };
+
+        let parsed = match lc(i) {
+            Err(_) if class.starts_with(|c: char| c.is_ascii_digit()) => dewey(i),
+            parsed => parsed,
+        };
+
+        let lc_letter = class.get(..1).is_some_and(|letter| Genre::parse_genre(letter).is_ok());
+        match parsed {
+            Err(_) if !lc_letter && SuDoc::parse_repairing(i).is_ok() => sudoc(i),
+            parsed => parsed,
+        }
+    }
+
+    /// The trailing note of the call number, whichever scheme it's in.
+    pub fn note(&self) -> Option<&Note<'a>> {
+        match self {
+            CallNumber::LC(lc) => lc.note.as_ref(),
+            CallNumber::Dewey(dewey) => dewey.note.as_ref(),
+            CallNumber::SuDoc(_) | CallNumber::Local(_) => None,
+        }
+    }
+}
+
+impl<'a> fmt::Display for CallNumber<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CallNumber::LC(lc) => write!(f, "{}", lc),
+            CallNumber::Dewey(dewey) => write!(f, "{}", dewey),
+            CallNumber::SuDoc(sudoc) => write!(f, "{}", sudoc),
+            CallNumber::Local(local) => write!(f, "{}", local),
+        }
+    }
+}
+
+/// Runs `parser`, also returning the slice of input it consumed.
+fn spanned<'a, O, F>(
+    parser: F,
+) -> impl Fn(&'a str) -> nom::IResult<&'a str, (O, &'a str), ErrReport>
+where
+    F: Fn(&'a str) -> nom::IResult<&'a str, O, ErrReport>,
+{
+    move |i| {
+        let (rest, output) = parser(i)?;
+        let span = &i[..i.len() - rest.len()];
+        Ok((rest, (output, span)))
+    }
+}
+
+/// Expands the end of a year range to a full year. Ranges like "1990-91" and
+/// "1999/00" abbreviate the end to the digits that changed, rolling over into
+/// the next century when those digits are smaller.
+/// Ends written in full that come before the start, like "1999-1990", are
+/// rejected, as are ends past 9999 that wouldn't fit in four digits.
+fn range_end(start: u16, end: u16) -> Option<u16> {
+    let end = match end {
+        0..=99 if end >= start % 100 => start - start % 100 + end,
+        0..=99 => start - start % 100 + 100 + end,
+        end if end >= start => end,
+        _ => return None,
+    };
+
+    Some(end).filter(|&end| end <= 9999)
+}
+
+/// Checks that a parser consumed all of `i` but whitespace, reporting what's
+/// left over instead of dropping it.
+fn finished(i: &str, rest: &str) -> Result<(), ErrReport> {
+    if rest.trim().is_empty() {
+        Ok(())
+    } else {
+        Err(ErrReport::leftover(rest).with_input(i))
+    }
+}
+
+fn into_report(e: nom::Err<ErrReport>) -> ErrReport {
+    match e {
+        nom::Err::Error(e) | nom::Err::Failure(e) => e,
+        _ => unreachable!(),
+    }
 }
 
 #[cfg(test)]
@@ -237,19 +957,35 @@ mod tests {
     fn test_full() {
         let lc = "TD 224 .C3 C3723 2009";
         let expected = LC {
+            prefix: None,
+            nlm: false,
             genre: Genre("TD"),
-            second: Second(224.0),
-            third: Third {
-                has_dot: true,
-                body: "C3",
+            second: Second {
+                integer: "224",
+                fraction: None,
             },
-            fourth: Some(Third {
-                has_dot: false,
-                body: "C3723",
-            }),
+            segments: vec![
+                Segment::Cutter(Cutter {
+                    has_dot: true,
+                    letters: "C",
+                    digits: "3",
+                    suffix: None,
+                }),
+                Segment::Cutter(Cutter {
+                    has_dot: false,
+                    letters: "C",
+                    digits: "3723",
+                    suffix: None,
+                }),
+            ],
             year: Some(Year {
                 year: 2009,
+                known: 4,
+                end: None,
+                open: false,
                 suffix: None,
+                inferred: false,
+                copyright: false,
             }),
             note: None,
         };
@@ -261,16 +997,29 @@ mod tests {
     fn test_first() {
         let lc = "GB 658 .C43 2005";
         let expected = LC {
+            prefix: None,
+            nlm: false,
             genre: Genre("GB"),
-            second: Second(658.0),
-            third: Third {
-                has_dot: true,
-                body: "C43",
+            second: Second {
+                integer: "658",
+                fraction: None,
             },
-            fourth: None,
+            segments: vec![
+                Segment::Cutter(Cutter {
+                    has_dot: true,
+                    letters: "C",
+                    digits: "43",
+                    suffix: None,
+                }),
+            ],
             year: Some(Year {
                 year: 2005,
+                known: 4,
+                end: None,
+                open: false,
                 suffix: None,
+                inferred: false,
+                copyright: false,
             }),
             note: None,
         };
@@ -282,16 +1031,29 @@ mod tests {
     fn test_suffix() {
         let lc = "GC 21.5 .S56 1988b";
         let expected = LC {
+            prefix: None,
+            nlm: false,
             genre: Genre("GC"),
-            second: Second(21.5),
-            third: Third {
-                has_dot: true,
-                body: "S56",
+            second: Second {
+                integer: "21",
+                fraction: Some("5"),
             },
-            fourth: None,
+            segments: vec![
+                Segment::Cutter(Cutter {
+                    has_dot: true,
+                    letters: "S",
+                    digits: "56",
+                    suffix: None,
+                }),
+            ],
             year: Some(Year {
                 year: 1988,
+                known: 4,
+                end: None,
+                open: false,
                 suffix: Some('b'),
+                inferred: false,
+                copyright: false,
             }),
             note: None,
         };
@@ -303,19 +1065,35 @@ mod tests {
     fn test_optspace() {
         let lc = "TD224.C3 C3723 2004";
         let expected = LC {
+            prefix: None,
+            nlm: false,
             genre: Genre("TD"),
-            second: Second(224.0),
-            third: Third {
-                has_dot: true,
-                body: "C3",
+            second: Second {
+                integer: "224",
+                fraction: None,
             },
-            fourth: Some(Third {
-                has_dot: false,
-                body: "C3723",
-            }),
+            segments: vec![
+                Segment::Cutter(Cutter {
+                    has_dot: true,
+                    letters: "C",
+                    digits: "3",
+                    suffix: None,
+                }),
+                Segment::Cutter(Cutter {
+                    has_dot: false,
+                    letters: "C",
+                    digits: "3723",
+                    suffix: None,
+                }),
+            ],
             year: Some(Year {
                 year: 2004,
+                known: 4,
+                end: None,
+                open: false,
                 suffix: None,
+                inferred: false,
+                copyright: false,
             }),
             note: None,
         };
@@ -328,16 +1106,29 @@ mod tests {
     fn extra_space() {
         let lc = "QC 920 .Z38 2009 ";
         let expected = LC {
+            prefix: None,
+            nlm: false,
             genre: Genre("QC"),
-            second: Second(920.0),
-            third: Third {
-                has_dot: true,
-                body: "Z38",
+            second: Second {
+                integer: "920",
+                fraction: None,
             },
-            fourth: None,
+            segments: vec![
+                Segment::Cutter(Cutter {
+                    has_dot: true,
+                    letters: "Z",
+                    digits: "38",
+                    suffix: None,
+                }),
+            ],
             year: Some(Year {
                 year: 2009,
+                known: 4,
+                end: None,
+                open: false,
                 suffix: None,
+                inferred: false,
+                copyright: false,
             }),
             note: None,
         };
@@ -351,13 +1142,21 @@ mod tests {
     fn missing_year() {
         let lc = "QC 183 .G675";
         let expected = LC {
+            prefix: None,
+            nlm: false,
             genre: Genre("QC"),
-            second: Second(183.0),
-            third: Third {
-                has_dot: true,
-                body: "G675",
+            second: Second {
+                integer: "183",
+                fraction: None,
             },
-            fourth: None,
+            segments: vec![
+                Segment::Cutter(Cutter {
+                    has_dot: true,
+                    letters: "G",
+                    digits: "675",
+                    suffix: None,
+                }),
+            ],
             year: None,
             note: None,
         };
@@ -371,19 +1170,35 @@ mod tests {
     fn double_dot() {
         let lc = "HD 1695 .K55 .V5 2010";
         let expected = LC {
+            prefix: None,
+            nlm: false,
             genre: Genre("HD"),
-            second: Second(1695.0),
-            third: Third {
-                has_dot: true,
-                body: "K55",
+            second: Second {
+                integer: "1695",
+                fraction: None,
             },
-            fourth: Some(Third {
-                has_dot: true,
-                body: "V5",
-            }),
+            segments: vec![
+                Segment::Cutter(Cutter {
+                    has_dot: true,
+                    letters: "K",
+                    digits: "55",
+                    suffix: None,
+                }),
+                Segment::Cutter(Cutter {
+                    has_dot: true,
+                    letters: "V",
+                    digits: "5",
+                    suffix: None,
+                }),
+            ],
             year: Some(Year {
                 year: 2010,
+                known: 4,
+                end: None,
+                open: false,
                 suffix: None,
+                inferred: false,
+                copyright: false,
             }),
             note: None,
         };
@@ -397,19 +1212,35 @@ mod tests {
     fn space_in_float() {
         let lc = "HD 1695 .55 .K55 .V5 2010";
         let expected = LC {
+            prefix: None,
+            nlm: false,
             genre: Genre("HD"),
-            second: Second(1695.55),
-            third: Third {
-                has_dot: true,
-                body: "K55",
+            second: Second {
+                integer: "1695",
+                fraction: Some("55"),
             },
-            fourth: Some(Third {
-                has_dot: true,
-                body: "V5",
-            }),
+            segments: vec![
+                Segment::Cutter(Cutter {
+                    has_dot: true,
+                    letters: "K",
+                    digits: "55",
+                    suffix: None,
+                }),
+                Segment::Cutter(Cutter {
+                    has_dot: true,
+                    letters: "V",
+                    digits: "5",
+                    suffix: None,
+                }),
+            ],
             year: Some(Year {
                 year: 2010,
+                known: 4,
+                end: None,
+                open: false,
                 suffix: None,
+                inferred: false,
+                copyright: false,
             }),
             note: None,
         };
@@ -422,19 +1253,35 @@ mod tests {
     fn offset_dots() {
         let lc = "HD 1695 .55. K55. V5 2010";
         let expected = LC {
+            prefix: None,
+            nlm: false,
             genre: Genre("HD"),
-            second: Second(1695.55),
-            third: Third {
-                has_dot: true,
-                body: "K55",
+            second: Second {
+                integer: "1695",
+                fraction: Some("55"),
             },
-            fourth: Some(Third {
-                has_dot: true,
-                body: "V5",
-            }),
+            segments: vec![
+                Segment::Cutter(Cutter {
+                    has_dot: true,
+                    letters: "K",
+                    digits: "55",
+                    suffix: None,
+                }),
+                Segment::Cutter(Cutter {
+                    has_dot: true,
+                    letters: "V",
+                    digits: "5",
+                    suffix: None,
+                }),
+            ],
             year: Some(Year {
                 year: 2010,
+                known: 4,
+                end: None,
+                open: false,
                 suffix: None,
+                inferred: false,
+                copyright: false,
             }),
             note: None,
         };
@@ -465,19 +1312,35 @@ mod tests {
         let lc = "TD 225 .S25 H26x 2002";
         dbg!(lc);
         let expected = LC {
+            prefix: None,
+            nlm: false,
             genre: Genre("TD"),
-            second: Second(225.0),
-            third: Third {
-                has_dot: true,
-                body: "S25",
+            second: Second {
+                integer: "225",
+                fraction: None,
             },
-            fourth: Some(Third {
-                has_dot: false,
-                body: "H26x",
-            }),
+            segments: vec![
+                Segment::Cutter(Cutter {
+                    has_dot: true,
+                    letters: "S",
+                    digits: "25",
+                    suffix: None,
+                }),
+                Segment::Cutter(Cutter {
+                    has_dot: false,
+                    letters: "H",
+                    digits: "26",
+                    suffix: Some("x"),
+                }),
+            ],
             year: Some(Year {
                 year: 2002,
+                known: 4,
+                end: None,
+                open: false,
                 suffix: None,
+                inferred: false,
+                copyright: false,
             }),
             note: None,
         };
@@ -492,19 +1355,35 @@ mod tests {
         let lc = "G 4364 .R6 .S6C3 2006";
         dbg!(lc);
         let expected = LC {
+            prefix: None,
+            nlm: false,
             genre: Genre("G"),
-            second: Second(4364.0),
-            third: Third {
-                has_dot: true,
-                body: "R6",
+            second: Second {
+                integer: "4364",
+                fraction: None,
             },
-            fourth: Some(Third {
-                has_dot: true,
-                body: "S6C3",
-            }),
+            segments: vec![
+                Segment::Cutter(Cutter {
+                    has_dot: true,
+                    letters: "R",
+                    digits: "6",
+                    suffix: None,
+                }),
+                Segment::Cutter(Cutter {
+                    has_dot: true,
+                    letters: "S",
+                    digits: "6",
+                    suffix: Some("C3"),
+                }),
+            ],
             year: Some(Year {
                 year: 2006,
+                known: 4,
+                end: None,
+                open: false,
                 suffix: None,
+                inferred: false,
+                copyright: false,
             }),
             note: None,
         };
@@ -519,18 +1398,34 @@ mod tests {
         let expected_round_trip = "LB 1028 .L43 2000 vol. tg";
         dbg!(lc);
         let expected = LC {
+            prefix: None,
+            nlm: false,
             genre: Genre("LB"),
-            second: Second(1028.0),
-            third: Third {
-                has_dot: true,
-                body: "L43",
+            second: Second {
+                integer: "1028",
+                fraction: None,
             },
-            fourth: None,
+            segments: vec![
+                Segment::Cutter(Cutter {
+                    has_dot: true,
+                    letters: "L",
+                    digits: "43",
+                    suffix: None,
+                }),
+            ],
             year: Some(Year {
                 year: 2000,
+                known: 4,
+                end: None,
+                open: false,
                 suffix: None,
+                inferred: false,
+                copyright: false,
+            }),
+            note: Some(Note {
+                parts: vec![NotePart::Other("vol."), NotePart::Other("tg")],
+                text: "vol. tg",
             }),
-            note: Some(Note("vol. tg")),
         };
 
         let lc = LC::maybe_parse(lc).unwrap().unwrap();
@@ -544,45 +1439,1095 @@ mod tests {
         let lc_string = "QB 46 .L744 v.82 2000";
         dbg!(lc_string);
         let expected = LC {
+            prefix: None,
+            nlm: false,
             genre: Genre("QB"),
-            second: Second(46.0),
-            third: Third {
-                has_dot: true,
-                body: "L744",
+            second: Second {
+                integer: "46",
+                fraction: None,
             },
-            fourth: Some(Third {
-                has_dot: true,
-                body: "v82",
+            segments: vec![
+                Segment::Cutter(Cutter {
+                    has_dot: true,
+                    letters: "L",
+                    digits: "744",
+                    suffix: None,
+                }),
+            ],
+            year: None,
+            note: Some(Note {
+                parts: vec![
+                    NotePart::Volume(82),
+                    NotePart::Years {
+                        start: 2000,
+                        end: None,
+                    },
+                ],
+                text: "v.82 2000",
             }),
+        };
+
+        let lc = LC::maybe_parse(lc_string).unwrap().unwrap();
+        assert_eq!(&expected, dbg!(&lc));
+        let round_trip = lc.to_string();
+        assert_eq!(lc_string, round_trip);
+    }
+
+    #[test]
+    fn cutter_order() {
+        let lhs = LC::maybe_parse("GB 658 .C43 2005").unwrap().unwrap();
+        let rhs = LC::maybe_parse("GB 658 .C5 2005").unwrap().unwrap();
+        assert!(lhs < rhs);
+    }
+
+    #[test]
+    fn shelf_order() {
+        let mut lcs = vec![
+            "QC 981.3 .C53 2006",
+            "QC 920 .Z38 2009",
+            "G 4364 .R6 .S6C3 2006",
+            "GB 658 .C43 2005",
+            "QC 981.3 .A38 1993",
+            "QC 981 .A38 1993",
+            "GB 658 .C43",
+            "QC 183 .G675",
+            "GB 1002.25 .W38 2009",
+            "GB 658 .C43 2005 vol. tg",
+        ];
+        let expected = vec![
+            "G 4364 .R6 .S6C3 2006",
+            "GB 658 .C43",
+            "GB 658 .C43 2005",
+            "GB 658 .C43 2005 vol. tg",
+            "GB 1002.25 .W38 2009",
+            "QC 183 .G675",
+            "QC 920 .Z38 2009",
+            "QC 981 .A38 1993",
+            "QC 981.3 .A38 1993",
+            "QC 981.3 .C53 2006",
+        ];
+
+        lcs.sort_by_key(|lc| LC::maybe_parse(lc).unwrap().unwrap());
+        assert_eq!(expected, lcs);
+    }
+
+    #[test]
+    fn error_reason() {
+        let err = LC::maybe_parse("TK 7820 1985").unwrap_err();
+        assert_eq!(
+            "unable to parse fields as an LC in Cutter at \"1985\"",
+            err.reason()
+        );
+
+        let err = LC::maybe_parse("HV 636 2005.N4 P44 2010").unwrap_err();
+        let diagnostic = err.diagnostic().unwrap();
+        assert_eq!(Component::Cutter, diagnostic.component);
+        assert_eq!("2005.N4", diagnostic.found);
+    }
+
+    #[test]
+    fn error_diagnostic() {
+        let err = LC::maybe_parse("QC circ. desk").unwrap_err();
+        let expected = Diagnostic {
+            component: Component::ClassNumber,
+            offset: 3,
+            expected: "a class number".to_string(),
+            found: "circ.".to_string(),
+        };
+        assert_eq!(Some(expected), err.diagnostic());
+    }
+
+    #[test]
+    fn error_snippet() {
+        let err = LC::maybe_parse("QC circ. desk").unwrap_err();
+        let report = format!("{:?}", err);
+        assert!(report.contains("| QC circ. desk\n"), "{}", report);
+        assert!(report.contains("|    ^^^^^\n"), "{}", report);
+    }
+
+    #[test]
+    fn repair_missing_spaces() {
+        let (lc, repairs) = LC::parse_repairing("TD224.C3 C3723 2004").unwrap();
+        let expected = vec![
+            Repair::InsertedSpace(Component::ClassNumber),
+            Repair::InsertedSpace(Component::Cutter),
+        ];
+        assert_eq!(expected, repairs);
+        assert_eq!("TD 224 .C3 C3723 2004", lc.to_string());
+    }
+
+    #[test]
+    fn repair_offset_dots() {
+        let (lc, repairs) = LC::parse_repairing("HD 1695 .55. K55. V5 2010").unwrap();
+        let expected = vec![
+            Repair::InsertedSpace(Component::Cutter),
+            Repair::RemovedSpaceInClassNumber,
+            Repair::RemovedSpaceAfterDot,
+        ];
+        assert_eq!(expected, repairs);
+        assert_eq!("HD 1695.55 .K55 .V5 2010", lc.to_string());
+    }
+
+    #[test]
+    fn repair_missing_dot() {
+        let (lc, repairs) = LC::parse_repairing("QC 920 Z38  2009").unwrap();
+        let expected = vec![
+            Repair::CollapsedSpaces(Component::Year),
+            Repair::InsertedDot,
+        ];
+        assert_eq!(expected, repairs);
+        assert_eq!("QC 920 .Z38 2009", lc.to_string());
+    }
+
+    #[test]
+    fn repair_lowercase_class() {
+        let lc = LC::maybe_parse("qc 5 .D5 2001").unwrap().unwrap();
+        assert_eq!("Physics", lc.genre.caption());
+
+        let (lc, repairs) = LC::parse_repairing("qc 5 .D5 2001").unwrap();
+        assert_eq!(vec![Repair::UppercasedClass], repairs);
+        assert_eq!("QC 5 .D5 2001", lc.to_string());
+    }
+
+    #[test]
+    fn repair_year_range() {
+        let (lc, repairs) = LC::parse_repairing("QC 5 .D5 1990/1991").unwrap();
+        assert_eq!(vec![Repair::ExpandedYearRange], repairs);
+        assert_eq!("QC 5 .D5 1990-1991", lc.to_string());
+
+        let (lc, repairs) = LC::parse_repairing("QC 5 .D5 1990-91a").unwrap();
+        assert_eq!(vec![Repair::ExpandedYearRange], repairs);
+        assert_eq!("QC 5 .D5 1990-1991a", lc.to_string());
+
+        let (dewey, repairs) = Dewey::parse_repairing_with("813.54 B2 1990-91", &Prefixes::default()).unwrap();
+        assert_eq!(vec![Repair::ExpandedYearRange], repairs);
+        assert_eq!("813.54 B2 1990-1991", dewey.to_string());
+    }
+
+    #[test]
+    fn repair_canonical() {
+        let (_, repairs) = LC::parse_repairing("TD 224 .C3 C3723 2009").unwrap();
+        assert!(repairs.is_empty());
+    }
+
+    #[test]
+    fn strict_canonical() {
+        let lc = LC::parse_strict("TD 224 .C3 C3723 2009").unwrap();
+        assert_eq!("TD 224 .C3 C3723 2009", lc.to_string());
+    }
+
+    #[test]
+    fn strict_violations() {
+        let cases = vec![
+            ("TD 224 C3 C3723 2009", Violation::MissingDot),
+            ("TD224 .C3 2009", Violation::MissingSpace(Component::ClassNumber)),
+            ("TD 224 .C3  2009", Violation::ExtraSpace(Component::Year)),
+            ("HD 1695 .55 .K55 2010", Violation::SpaceInClassNumber),
+            ("HD 1695.55 . K55 2010", Violation::SpaceAfterDot),
+            ("QB 46 .L744 v82 2000", Violation::LowercaseCutter("v82".to_string())),
+            ("QB 46 .LA 2000", Violation::CutterWithoutDigit("LA".to_string())),
+            ("qb 46 .L744 2000", Violation::LowercaseClass),
+            ("QC 5 .D5 1990-91", Violation::YearRange),
+            ("QC 5 .D5 1990/1991", Violation::YearRange),
+        ];
+
+        for (lc, expected) in cases {
+            let err = LC::parse_strict(lc).unwrap_err();
+            assert_eq!(Some(&expected), err.violation(), "{}", lc);
+        }
+    }
+
+    #[test]
+    fn genre_caption() {
+        let lc = LC::maybe_parse("QC 920 .Z38 2009").unwrap().unwrap();
+        assert_eq!("Physics", lc.genre.caption());
+    }
+
+    #[test]
+    fn unknown_genre() {
+        let err = LC::maybe_parse("QQ 920 .Z38 2009").unwrap_err();
+        assert_eq!(Component::Genre, err.diagnostic().unwrap().component);
+    }
+
+    // Row { lc: "KJV 131 .V33 S73 2010" }
+    #[test]
+    fn three_letter_genre() {
+        let lc = "KFX 1234 .A1 2001";
+        let expected = LC {
+            prefix: None,
+            nlm: false,
+            genre: Genre("KFX"),
+            second: Second {
+                integer: "1234",
+                fraction: None,
+            },
+            segments: vec![
+                Segment::Cutter(Cutter {
+                    has_dot: true,
+                    letters: "A",
+                    digits: "1",
+                    suffix: None,
+                }),
+            ],
             year: Some(Year {
-                year: 2000,
+                year: 2001,
+                known: 4,
+                end: None,
+                open: false,
                 suffix: None,
+                inferred: false,
+                copyright: false,
             }),
             note: None,
         };
 
-        let lc = LC::maybe_parse(lc_string).unwrap().unwrap();
+        let lc = LC::maybe_parse(lc).unwrap().unwrap();
         assert_eq!(&expected, dbg!(&lc));
-        let round_trip = lc.to_string();
-        assert_eq!(lc_string, round_trip);
+        assert_eq!("KFX 1234 .A1 2001", lc.to_string());
+
+        let lc = LC::maybe_parse("DJK 49 .B3").unwrap().unwrap();
+        assert_eq!(Genre("DJK"), lc.genre);
+        assert_eq!("DJK 49 .B3", lc.to_string());
+
+        let lc = LC::maybe_parse("KJV 131 .V33 S73 2010").unwrap().unwrap();
+        assert_eq!("Law of France", lc.genre.caption());
+        assert_eq!(2, lc.cutters().count());
+        assert_eq!("KJV 131 .V33 S73 2010", lc.to_string());
+
+        for &lc in &["KNX 2016 .A1 2001", "KLA 1500 .R8 1999", "KPA 3200 .K5 2005"] {
+            assert_eq!(lc, LC::maybe_parse(lc).unwrap().unwrap().to_string());
+        }
+    }
+
+    #[test]
+    fn three_letter_order() {
+        let mut lcs = vec!["KG 49 .B3", "KFX 1234 .A1", "KF 9000 .C5"];
+        lcs.sort_by_key(|lc| LC::maybe_parse(lc).unwrap().unwrap());
+        assert_eq!(vec!["KF 9000 .C5", "KFX 1234 .A1", "KG 49 .B3"], lcs);
+    }
+
+    #[test]
+    fn exact_class_number() {
+        let lc = LC::maybe_parse("GC 21.50 .S56 1988b").unwrap().unwrap();
+        assert_eq!(
+            Second {
+                integer: "21",
+                fraction: Some("50"),
+            },
+            lc.second
+        );
+        assert_eq!("GC 21.50 .S56 1988b", lc.to_string());
     }
 
-    // Test case no longer necessary
-    // #[test]
-    // //Row "Circ. desk"
-    // fn only_text() {
-    //     let lc = "Circ. desk";
-    //     dbg!(lc);
-    //     let expected = LC {
-    //         genre: Genre(None),
-    //         second: Second(None),
-    //         third: Third(None),
-    //         fourth: None,
-    //         year: None,
-    //         note: "Circ. desk",
-    //     };
-
-    //     let (_, lc) = LC::parse_lc(lc).unwrap();
-    //     assert_eq!(expected,dbg!(lc));
-    // }
+    #[test]
+    fn class_number_order() {
+        let parse = |second| Second::parse_second(second).unwrap().1;
+        assert!(parse("3") < parse("21.5"));
+        assert!(parse("21.5") < parse("21.50"));
+        assert!(parse("21.50") < parse("21.6"));
+        assert!(parse("21.123") < parse("21.5"));
+        assert!(parse("021") < parse("100"));
+    }
+
+    #[test]
+    fn interleaved_date() {
+        let lc = "G 3701 .S12 1995 .U5";
+        let expected = LC {
+            prefix: None,
+            nlm: false,
+            genre: Genre("G"),
+            second: Second {
+                integer: "3701",
+                fraction: None,
+            },
+            segments: vec![
+                Segment::Cutter(Cutter {
+                    has_dot: true,
+                    letters: "S",
+                    digits: "12",
+                    suffix: None,
+                }),
+                Segment::Year(Year {
+                    year: 1995,
+                    known: 4,
+                    end: None,
+                    open: false,
+                    suffix: None,
+                    inferred: false,
+                    copyright: false,
+                }),
+                Segment::Cutter(Cutter {
+                    has_dot: true,
+                    letters: "U",
+                    digits: "5",
+                    suffix: None,
+                }),
+            ],
+            year: None,
+            note: None,
+        };
+
+        let lc = LC::maybe_parse(lc).unwrap().unwrap();
+        assert_eq!(&expected, dbg!(&lc));
+        assert_eq!("G 3701 .S12 1995 .U5", lc.to_string());
+    }
+
+    #[test]
+    fn three_cutters() {
+        let lc = LC::maybe_parse("PS 3545 .I345 Z5 A3 2001").unwrap().unwrap();
+        let cutters = lc.cutters().map(ToString::to_string).collect::<Vec<_>>();
+        assert_eq!(vec![".I345", "Z5", "A3"], cutters);
+        assert_eq!(
+            Some(Year {
+                year: 2001,
+                known: 4,
+                end: None,
+                open: false,
+                suffix: None,
+                inferred: false,
+                copyright: false,
+            }),
+            lc.year
+        );
+        assert_eq!(None, lc.note);
+    }
+
+    #[test]
+    fn cutter_shape() {
+        let (_, cutter) = Cutter::parse_cutter(" H26x").unwrap();
+        assert_eq!("H", cutter.letters());
+        assert_eq!("26", cutter.digits());
+        assert_eq!(Some("x"), cutter.suffix());
+
+        let parse = |cutter| Cutter::parse_cutter(cutter).unwrap().1;
+        assert!(parse(".C43") < parse(".C5"));
+        assert!(parse(".C5") < parse(".C50"));
+        assert!(parse(".C5") < parse(".CA3"));
+        assert!(parse("H26") < parse("H26x"));
+    }
+
+    #[test]
+    fn structured_note() {
+        let lc = LC::maybe_parse("Q 182.3 .R69 2007 v.2 pt. 1 c.3 suppl. index 1998/99").unwrap().unwrap();
+        let note = lc.note.as_ref().unwrap();
+        let expected = [
+            NotePart::Volume(2),
+            NotePart::Part(1),
+            NotePart::Copy(3),
+            NotePart::Supplement(None),
+            NotePart::Index,
+            NotePart::Years {
+                start: 1998,
+                end: Some(1999),
+            },
+        ];
+        assert_eq!(&expected[..], note.parts());
+        assert!(note.is_recognized());
+        assert_eq!("Q 182.3 .R69 2007 v.2 pt. 1 c.3 suppl. index 1998/99", lc.to_string());
+
+        let lc = LC::maybe_parse("LB 1028 .L43 2000 vol. tg").unwrap().unwrap();
+        assert!(!lc.note.unwrap().is_recognized());
+
+        let lc = LC::maybe_parse("QA 76 .A1 2001 index 1999/00").unwrap().unwrap();
+        let note = lc.note.unwrap();
+        let expected = NotePart::Years {
+            start: 1999,
+            end: Some(2000),
+        };
+        assert_eq!(&[NotePart::Index, expected][..], note.parts());
+        assert_eq!("1999-2000", expected.to_string());
+
+        let lc = LC::maybe_parse("QA 76 .A1 2001 0800 9999-00").unwrap().unwrap();
+        let note = lc.note.unwrap();
+        let expected = [
+            NotePart::Years {
+                start: 800,
+                end: None,
+            },
+            NotePart::Other("9999-00"),
+        ];
+        assert_eq!(&expected[..], note.parts());
+        assert_eq!("0800", expected[0].to_string());
+    }
+
+    // Row { lc: "T 1 .S5 Index 1948-78" }
+    #[test]
+    fn index_ends_cutters() {
+        let lc = LC::maybe_parse("T 1 .S5 Index 1948-78").unwrap().unwrap();
+        assert_eq!(1, lc.cutters().count());
+        assert_eq!(None, lc.year);
+        let expected = [
+            NotePart::Index,
+            NotePart::Years {
+                start: 1948,
+                end: Some(1978),
+            },
+        ];
+        assert_eq!(&expected[..], lc.note.as_ref().unwrap().parts());
+        assert!(LC::parse_strict("T 1 .S5 Index 1948-78").is_ok());
+    }
+
+    #[test]
+    fn volume_order() {
+        let mut lcs = vec![
+            "Q 182.3 .R69 2007 v.10",
+            "Q 182.3 .R69 2007 v.2 c.2",
+            "Q 182.3 .R69 2007",
+            "Q 182.3 .R69 2007 v.2",
+        ];
+        lcs.sort_by_key(|lc| LC::maybe_parse(lc).unwrap().unwrap());
+        let expected = vec![
+            "Q 182.3 .R69 2007",
+            "Q 182.3 .R69 2007 v.2",
+            "Q 182.3 .R69 2007 v.2 c.2",
+            "Q 182.3 .R69 2007 v.10",
+        ];
+        assert_eq!(expected, lcs);
+    }
+
+    #[test]
+    fn location_prefix() {
+        let lc = "Ref QC 5 .D5 2001";
+        let expected = LC {
+            prefix: Some(Prefix("Ref")),
+            nlm: false,
+            genre: Genre("QC"),
+            second: Second {
+                integer: "5",
+                fraction: None,
+            },
+            segments: vec![Segment::Cutter(Cutter {
+                has_dot: true,
+                letters: "D",
+                digits: "5",
+                suffix: None,
+            })],
+            year: Some(Year {
+                year: 2001,
+                known: 4,
+                end: None,
+                open: false,
+                suffix: None,
+                inferred: false,
+                copyright: false,
+            }),
+            note: None,
+        };
+        let (_, parsed) = LC::parse_lc(lc).unwrap();
+        assert_eq!(expected, parsed);
+        assert_eq!(lc, parsed.to_string());
+
+        let (lc, repairs) = LC::parse_repairing("Oversize.  G 1021 .N38 1999").unwrap();
+        assert_eq!("Oversize. G 1021 .N38 1999", lc.to_string());
+        assert_eq!(vec![Repair::CollapsedSpaces(Component::Genre)], repairs);
+    }
+
+    #[test]
+    fn custom_prefixes() {
+        // "CD" is in the default vocabulary but is also a class
+        let lc = LC::maybe_parse("CD 921 .A1 1990").unwrap().unwrap();
+        assert_eq!(None, lc.prefix);
+        assert_eq!("CD", lc.genre.0);
+
+        let lc = LC::maybe_parse("DVD CD 921 .A1 1990").unwrap().unwrap();
+        assert_eq!(Some(Prefix("DVD")), lc.prefix);
+
+        assert!(LC::maybe_parse("Atlas G 1021 .N38 1999").is_err());
+
+        let mut prefixes = Prefixes::none();
+        prefixes.insert("Atlas");
+        let lc = LC::maybe_parse_with("Atlas G 1021 .N38 1999", &prefixes).unwrap().unwrap();
+        assert_eq!(Some(Prefix("Atlas")), lc.prefix);
+        assert!(LC::maybe_parse_with("Ref QC 5 .D5 2001", &prefixes).is_err());
+    }
+
+    #[test]
+    fn prefix_order() {
+        let mut lcs = vec![
+            "Ref QC 5 .D5 2001",
+            "QC 5 .D5 2001",
+            "Oversize A 5 .D5 2001",
+            "REF. QB 5 .D5 2001",
+            "Z 5 .D5 2001",
+        ];
+        lcs.sort_by_key(|lc| LC::maybe_parse(lc).unwrap().unwrap());
+        let expected = vec![
+            "QC 5 .D5 2001",
+            "Z 5 .D5 2001",
+            "Oversize A 5 .D5 2001",
+            "REF. QB 5 .D5 2001",
+            "Ref QC 5 .D5 2001",
+        ];
+        assert_eq!(expected, lcs);
+    }
+
+    #[test]
+    fn dewey() {
+        let dewey = "813.54 S643w 2001";
+        let expected = Dewey {
+            prefix: None,
+            class: Second {
+                integer: "813",
+                fraction: Some("54"),
+            },
+            cutter: Some(Cutter {
+                has_dot: false,
+                letters: "S",
+                digits: "643",
+                suffix: Some("w"),
+            }),
+            year: Some(Year {
+                year: 2001,
+                known: 4,
+                end: None,
+                open: false,
+                suffix: None,
+                inferred: false,
+                copyright: false,
+            }),
+            note: None,
+        };
+        let (_, parsed) = Dewey::parse_dewey(dewey).unwrap();
+        assert_eq!(expected, parsed);
+        assert_eq!(dewey, parsed.to_string());
+
+        let parsed = Dewey::maybe_parse("Ref 551.5 CLA v.2").unwrap().unwrap();
+        assert_eq!(Some(Prefix("Ref")), parsed.prefix);
+        assert_eq!("CLA", parsed.cutter.unwrap().letters);
+        assert_eq!(&[NotePart::Volume(2)], parsed.note.unwrap().parts());
+
+        let parsed = Dewey::maybe_parse("551.5 2001").unwrap().unwrap();
+        assert_eq!(None, parsed.cutter);
+    }
+
+    #[test]
+    fn dewey_order() {
+        let mut deweys = vec!["551.5 CLA", "551.46 ZIM", "551 ABE", "551.5 C593"];
+        deweys.sort_by_key(|dewey| Dewey::maybe_parse(dewey).unwrap().unwrap());
+        let expected = vec!["551 ABE", "551.46 ZIM", "551.5 C593", "551.5 CLA"];
+        assert_eq!(expected, deweys);
+    }
+
+    #[test]
+    fn dewey_diagnostic() {
+        assert!(Dewey::maybe_parse("5515 CLA").is_err());
+
+        let e = Dewey::maybe_parse("55.5 CLA").unwrap_err();
+        assert_eq!(r#"unable to parse fields as a Dewey number in Second at "55.5 CLA""#, e.reason());
+        let diagnostic = e.diagnostic().unwrap();
+        assert_eq!(Component::ClassNumber, diagnostic.component);
+        assert_eq!(0, diagnostic.offset);
+
+        let (_, repairs) = Dewey::parse_repairing_with("551.5  CLA", &Prefixes::default()).unwrap();
+        assert_eq!(vec![Repair::CollapsedSpaces(Component::Cutter)], repairs);
+    }
+
+    #[test]
+    fn detect_scheme() {
+        let parse = |i| CallNumber::maybe_parse(i).unwrap().unwrap();
+
+        assert!(matches!(parse("QC 5 .D5 2001"), CallNumber::LC(_)));
+        assert!(matches!(parse("551.5 CLA"), CallNumber::Dewey(_)));
+        assert!(matches!(parse("Ref 551.5 CLA"), CallNumber::Dewey(_)));
+        assert!(matches!(parse("CD 921 .A1 1990"), CallNumber::LC(_)));
+        assert_eq!("551.5 CLA", parse("551.5 CLA").to_string());
+
+        let e = CallNumber::maybe_parse("5515 CLA").unwrap_err();
+        assert!(e.reason().starts_with("unable to parse fields as a Dewey number"));
+        let e = CallNumber::maybe_parse("QC circ. desk").unwrap_err();
+        assert!(e.reason().starts_with("unable to parse fields as an LC"));
+
+        let mut numbers = vec!["551.5 CLA", "QC 5 .D5 2001", "300 ABE", "A 5 .D5 2001"];
+        numbers.sort_by_key(|number| parse(number));
+        let expected = vec!["A 5 .D5 2001", "QC 5 .D5 2001", "300 ABE", "551.5 CLA"];
+        assert_eq!(expected, numbers);
+    }
+
+    #[test]
+    fn nlm_class() {
+        let lc = "WB 100 .H45 2010";
+        let expected = LC {
+            prefix: None,
+            nlm: true,
+            genre: Genre("WB"),
+            second: Second {
+                integer: "100",
+                fraction: None,
+            },
+            segments: vec![Segment::Cutter(Cutter {
+                has_dot: true,
+                letters: "H",
+                digits: "45",
+                suffix: None,
+            })],
+            year: Some(Year {
+                year: 2010,
+                known: 4,
+                end: None,
+                open: false,
+                suffix: None,
+                inferred: false,
+                copyright: false,
+            }),
+            note: None,
+        };
+        let (_, parsed) = LC::parse_lc(lc).unwrap();
+        assert_eq!(expected, parsed);
+        assert_eq!("Practice of Medicine", parsed.genre.caption());
+        assert_eq!(lc, parsed.to_string());
+
+        let e = LC::maybe_parse("WB circ. desk").unwrap_err();
+        assert!(e.reason().starts_with("unable to parse fields as an NLM number"));
+
+        let parsed = LC::maybe_parse("QX 920 .Z38 2009").unwrap().unwrap();
+        assert!(parsed.nlm);
+        assert_eq!("Parasitology. Disease Vectors", parsed.genre.caption());
+
+        let parsed = LC::maybe_parse("QC 920 .Z38 2009").unwrap().unwrap();
+        assert!(!parsed.nlm);
+        assert!(!parsed.genre.is_nlm());
+    }
+
+    #[test]
+    fn nlm_order() {
+        let mut lcs = vec!["W 1 .A1 2001", "QS 4 .A1 2001", "Z 5 .A1 2001", "QR 5 .A1 2001"];
+        lcs.sort_by_key(|lc| LC::maybe_parse(lc).unwrap().unwrap());
+        let expected = vec!["QR 5 .A1 2001", "Z 5 .A1 2001", "QS 4 .A1 2001", "W 1 .A1 2001"];
+        assert_eq!(expected, lcs);
+    }
+
+    #[test]
+    fn sudoc() {
+        let sudoc = "I 19.2:G 29/2001";
+        let element = |separator, value| Element { value, separator };
+        let expected = SuDoc {
+            stem: vec![
+                element(None, Piece::Letters("I")),
+                element(Some(' '), Piece::Number("19")),
+                element(Some('.'), Piece::Number("2")),
+            ],
+            book: Some(vec![
+                element(None, Piece::Letters("G")),
+                element(Some(' '), Piece::Number("29")),
+                element(Some('/'), Piece::Number("2001")),
+            ]),
+        };
+        let (_, parsed) = SuDoc::parse_sudoc(sudoc).unwrap();
+        assert_eq!(expected, parsed);
+        assert_eq!(sudoc, parsed.to_string());
+
+        let parsed = SuDoc::maybe_parse("Y 4.AG 8/1:104-45").unwrap().unwrap();
+        assert_eq!("Y 4.AG 8/1:104-45", parsed.to_string());
+        let parsed = SuDoc::maybe_parse("A 1.1:").unwrap().unwrap();
+        assert_eq!(Some(vec![]), parsed.book);
+
+        let (parsed, repairs) = SuDoc::parse_repairing("I19.2:  G  29/2001").unwrap();
+        assert_eq!(sudoc, parsed.to_string());
+        let expected = vec![
+            Repair::InsertedSpace(Component::Stem),
+            Repair::CollapsedSpaces(Component::BookNumber),
+        ];
+        assert_eq!(expected, repairs);
+    }
+
+    #[test]
+    fn sudoc_order() {
+        let mut sudocs = vec![
+            "I 19.2/2:A 1",
+            "I 19.10:A 1",
+            "I 19.2:G 29/2001",
+            "I 19.2:29",
+            "I 19.2",
+            "I 19.2:G 29/999",
+            "C 3.134:A 1",
+            "I 19.2:G 3",
+        ];
+        sudocs.sort_by_key(|sudoc| SuDoc::maybe_parse(sudoc).unwrap().unwrap());
+        let expected = vec![
+            "C 3.134:A 1",
+            "I 19.2",
+            "I 19.2:G 3",
+            "I 19.2:G 29/999",
+            "I 19.2:G 29/2001",
+            "I 19.2:29",
+            "I 19.2/2:A 1",
+            "I 19.10:A 1",
+        ];
+        assert_eq!(expected, sudocs);
+    }
+
+    #[test]
+    fn sudoc_diagnostic() {
+        let e = SuDoc::maybe_parse("I 19:G 29").unwrap_err();
+        assert_eq!(Component::Stem, e.diagnostic().unwrap().component);
+        let e = SuDoc::maybe_parse("I 19..2:G 29").unwrap_err();
+        let diagnostic = e.diagnostic().unwrap();
+        assert_eq!(Component::Stem, diagnostic.component);
+        assert_eq!(4, diagnostic.offset);
+
+        let (parsed, repairs) = SuDoc::parse_repairing("I 19.2 :G 29").unwrap();
+        assert_eq!("I 19.2:G 29", parsed.to_string());
+        assert_eq!(vec![Repair::CollapsedSpaces(Component::BookNumber)], repairs);
+        assert!(e.reason().starts_with("unable to parse fields as a SuDoc number in Stem"));
+
+        let e = SuDoc::maybe_parse("I 19.2:G (29)").unwrap_err();
+        let diagnostic = e.diagnostic().unwrap();
+        assert_eq!(Component::BookNumber, diagnostic.component);
+        assert_eq!(9, diagnostic.offset);
+
+        let parsed = CallNumber::maybe_parse("I 19.2:G 29/2001").unwrap().unwrap();
+        assert!(matches!(parsed, CallNumber::SuDoc(_)));
+        let parsed = CallNumber::maybe_parse("I 19.2").unwrap().unwrap();
+        assert!(matches!(parsed, CallNumber::SuDoc(_)));
+        assert!(matches!(Parser::new().parse("Y 4.AG 8/1"), Ok(CallNumber::SuDoc(_))));
+        let e = Parser::new().parse("QQ 5.D5").unwrap_err();
+        assert_eq!(Component::Genre, e.diagnostic().unwrap().component);
+        assert!(Parser::new().parse("ZZ 12.A3").is_err());
+        let mut strict = Parser::new();
+        strict.strict(true);
+        for i in &["QC 5.D5", "QC 5.D5 2001", "PS 3545.I345"] {
+            let e = strict.parse(i).unwrap_err();
+            assert_eq!(Some(&Violation::MissingSpace(Component::Cutter)), e.violation(), "{}", i);
+        }
+        let e = CallNumber::maybe_parse("QC circ. desk").unwrap_err();
+        assert_eq!(Component::ClassNumber, e.diagnostic().unwrap().component);
+    }
+
+    #[test]
+    fn early_years() {
+        let lc = LC::maybe_parse("QC 5 .D5 0800").unwrap().unwrap();
+        assert_eq!("QC 5 .D5 0800", lc.to_string());
+        let lc = LC::maybe_parse("QC 5 .D5 0999a").unwrap().unwrap();
+        assert_eq!("QC 5 .D5 0999a", lc.to_string());
+        let lc = LC::builder().class("QC").class_number("5").cutter(".D5").year("0800").build().unwrap();
+        assert_eq!(800, lc.year.as_ref().unwrap().first());
+        assert_eq!("QC 5 .D5 0800", lc.to_string());
+    }
+
+    #[test]
+    fn year_forms() {
+        let year = |i| Year::parse_year(i).unwrap().1;
+
+        let expected = Year {
+            year: 1990,
+            known: 4,
+            end: Some(1991),
+            open: false,
+            suffix: None,
+            inferred: false,
+            copyright: false,
+        };
+        assert_eq!(expected, year("1990-1991"));
+        assert_eq!(expected, year("1990-91"));
+        assert_eq!(2000, year("1999-00").last());
+        assert!(Year::parse_year("9999-00").is_err());
+        let lc = LC::maybe_parse("QC 5 .D5 9999-00").unwrap().unwrap();
+        assert_eq!(None, lc.year);
+        assert!(!lc.note.unwrap().is_recognized());
+
+        let inferred = year("[2005]");
+        assert!(inferred.is_inferred());
+        assert_eq!(2005, inferred.first());
+
+        let copyright = year("c2001");
+        assert!(copyright.is_copyright());
+        assert_eq!(2001, copyright.first());
+
+        let decade = year("19--");
+        assert!(decade.is_uncertain());
+        assert_eq!((1900, 1999), (decade.first(), decade.last()));
+        assert_eq!((1990, 1999), (year("199-").first(), year("199-").last()));
+
+        assert_eq!(Some('a'), year("2002a").suffix());
+
+        for canonical in &["1990-1991", "[2005]", "c2001", "19--", "199-", "2002a", "[c1999b]"] {
+            assert_eq!(*canonical, year(canonical).to_string());
+        }
+        assert_eq!("1990-1991", year("1990-91").to_string());
+
+        let slash = year("1990/91");
+        assert_eq!((1990, 1991), (slash.first(), slash.last()));
+        assert_eq!("1990-1991", slash.to_string());
+
+        let open = year("1984-");
+        assert!(open.is_open());
+        assert_eq!((1984, u16::MAX), (open.first(), open.last()));
+        assert_eq!("1984-", open.to_string());
+        assert!(year("1984") < open && open < year("1984-1990"));
+
+        assert!(Year::parse_year("1999-1990").is_err());
+        assert!(Year::parse_year("1---").is_err());
+
+        let lc = LC::maybe_parse("QC 5 .D5 1990/91").unwrap().unwrap();
+        assert_eq!((None, 1991), (lc.note.as_ref(), lc.year.as_ref().unwrap().last()));
+        // Row { lc: "QE 79 .C47 1984-" }
+        let lc = LC::maybe_parse("QE 79 .C47 1984-").unwrap().unwrap();
+        assert_eq!(None, lc.note);
+        assert_eq!("QE 79 .C47 1984-", lc.to_string());
+        assert!(Year::parse_year("[2005").is_err());
+    }
+
+    #[test]
+    fn year_order() {
+        let mut years = vec!["2002a", "1990-1991", "19--", "[2005]", "1990", "c2001", "199-", "2002"];
+        years.sort_by_key(|year| Year::parse_year(year).unwrap().1);
+        let expected = vec!["19--", "199-", "1990", "1990-1991", "c2001", "2002", "2002a", "[2005]"];
+        assert_eq!(expected, years);
+    }
+
+    #[test]
+    fn year_in_call_number() {
+        for lc in &[
+            "QC 5 .D5 1990-1991",
+            "QC 5 .D5 [2005]",
+            "QC 5 .D5 c2001",
+            "QC 5 .D5 19--",
+            "QC 5 .D5 S6 c2001",
+        ] {
+            let parsed = LC::maybe_parse(lc).unwrap().unwrap();
+            assert!(parsed.year.is_some(), "{} has no year", lc);
+            assert_eq!(None, parsed.note, "{} has a note", lc);
+            assert_eq!(*lc, parsed.to_string());
+        }
+
+        let parsed = Dewey::maybe_parse("551.5 CLA [2005]").unwrap().unwrap();
+        assert!(parsed.year.unwrap().is_inferred());
+    }
+
+    #[test]
+    fn owned() {
+        let text = String::from("Ref QB 46 .L744 v.82 2000");
+        let buf = LC::maybe_parse(&text).unwrap().unwrap().to_buf().unwrap();
+        drop(text);
+
+        let lc = std::thread::spawn(move || buf).join().unwrap();
+        assert_eq!("Ref QB 46 .L744 v.82 2000", lc.to_string());
+        assert_eq!(Some(Prefix("Ref")), lc.as_lc().prefix);
+
+        let mut prefixes = Prefixes::none();
+        prefixes.insert("Atlas");
+        let atlas = LC::maybe_parse_with("Atlas G 1021 .N38 1990-91", &prefixes).unwrap().unwrap();
+        let buf = atlas.to_buf().unwrap();
+        assert_eq!("Atlas G 1021 .N38 1990-1991", buf.as_str());
+        assert_eq!(atlas, buf.as_lc());
+
+        let mut bufs = vec!["QC 5 .D5 2001", "Ref A 1 .B2 2001", "A 5 .D5 2001"]
+            .into_iter()
+            .map(|lc| LC::maybe_parse(lc).unwrap().unwrap().to_buf().unwrap())
+            .collect::<Vec<_>>();
+        bufs.sort();
+        let bufs = bufs.iter().map(LCBuf::as_str).collect::<Vec<_>>();
+        assert_eq!(vec!["A 5 .D5 2001", "QC 5 .D5 2001", "Ref A 1 .B2 2001"], bufs);
+
+        let lc = LC::builder().class("QC").class_number("5").cutter("d5").build().unwrap();
+        assert_eq!(lc, lc.to_buf().unwrap().as_lc());
+
+        let mut lc = LC::maybe_parse("QC 5 .D5 2001").unwrap().unwrap();
+        lc.segments.push(Segment::Year(*lc.year.as_ref().unwrap()));
+        assert!(lc.to_buf().is_err());
+    }
+
+    #[test]
+    fn serde_string() {
+        let lc = LC::maybe_parse("QB 46 .L744 2000").unwrap().unwrap();
+        let json = serde_json::to_string(&lc).unwrap();
+        assert_eq!(r#""QB 46 .L744 2000""#, json);
+        assert_eq!(lc, serde_json::from_str::<LC<'_>>(&json).unwrap());
+
+        let buf = serde_json::from_str::<LCBuf>(&json).unwrap();
+        assert_eq!(lc, buf.as_lc());
+        assert_eq!(json, serde_json::to_string(&buf).unwrap());
+
+        assert!(serde_json::from_str::<LCBuf>(r#""QC circ. desk""#).is_err());
+    }
+
+    #[test]
+    fn serde_components() {
+        #[derive(serde::Serialize, serde::Deserialize)]
+        struct Row {
+            #[serde(with = "components")]
+            lc: LCBuf,
+        }
+
+        let lc = LC::maybe_parse("WB 100 .H45 H6 2010 c.2").unwrap().unwrap();
+        let json = serde_json::to_string(&Row { lc: lc.to_buf().unwrap() }).unwrap();
+        let expected = r#"{"lc":{"class":"WB","nlm":true,"class_number":"100","segments":[".H45","H6"],"year":"2010","note":"c.2"}}"#;
+        assert_eq!(expected, json);
+
+        let row = serde_json::from_str::<Row>(&json).unwrap();
+        assert_eq!(lc, row.lc.as_lc());
+        let row = serde_json::from_str::<Row>(r#"{"lc":"WB 100 .H45 H6 2010 c.2"}"#).unwrap();
+        assert_eq!(lc, row.lc.as_lc());
+
+        let split = r#"{"lc":{"class":"WB","class_number":"100","segments":[".H45 H6"]}}"#;
+        assert!(serde_json::from_str::<Row>(split).is_err());
+    }
+
+    #[test]
+    fn accessors() {
+        let lc = LC::maybe_parse("Ref HD 1695.55 .K55 H6x c2001 v.2").unwrap().unwrap();
+        assert_eq!(Some("Ref"), lc.prefix.as_ref().map(Prefix::as_str));
+        assert_eq!("HD", lc.genre.as_str());
+        assert_eq!(("1695", Some("55")), (lc.second.integer(), lc.second.fraction()));
+
+        let cutters = lc.cutters().collect::<Vec<_>>();
+        assert_eq!(("K", "55", None), (cutters[0].letters(), cutters[0].digits(), cutters[0].suffix()));
+        assert_eq!(("H", "6", Some("x")), (cutters[1].letters(), cutters[1].digits(), cutters[1].suffix()));
+
+        let year = lc.year.as_ref().unwrap();
+        assert_eq!(2001, year.first());
+        assert!(year.is_copyright());
+        assert_eq!("v.2", lc.note.as_ref().unwrap().as_str());
+    }
+
+    #[test]
+    fn builder() {
+        let built = LC::builder()
+            .prefix("Ref")
+            .class("HD")
+            .class_number("1695.55")
+            .cutter(".K55")
+            .cutter("H6x")
+            .year("c2001")
+            .note("v.2")
+            .build()
+            .unwrap();
+        let parsed = LC::maybe_parse("Ref HD 1695.55 .K55 H6x c2001 v.2").unwrap().unwrap();
+        assert_eq!(parsed, built);
+
+        let mut builder = LCBuilder::new();
+        builder.class("G").class_number("3701").cutter(".S12").date("1995");
+        let e = builder.build().unwrap_err();
+        assert_eq!("a date between cutters has to be followed by a cutter", e.reason());
+        builder.cutter(".U5");
+        assert_eq!("G 3701 .S12 1995 .U5", builder.build().unwrap().to_string());
+
+        let e = LC::builder().class("QC").class_number("5").build().unwrap_err();
+        assert_eq!("missing cutter", e.reason());
+
+        let e = LC::builder().class("QQ").class_number("5").cutter(".D5").build().unwrap_err();
+        assert_eq!(Component::Genre, e.diagnostic().unwrap().component);
+
+        let e = LC::builder().class("QC").class_number("5").cutter(".D5 2001").build().unwrap_err();
+        let diagnostic = e.diagnostic().unwrap();
+        assert_eq!(Component::Cutter, diagnostic.component);
+        assert_eq!("2001", diagnostic.found);
+
+        let note = LC::builder().class("QC").class_number("5").cutter(".D5").note("A1").build();
+        assert!(note.is_err());
+        let mut builder = LCBuilder::new();
+        builder.class("G").class_number("3701").cutter(".S12").date("1995").cutter("u5");
+        assert!(builder.build().is_err());
+
+        let lc = LC::builder().class("QC").class_number("5").cutter(".D5").note("c.2").build().unwrap();
+        assert_eq!(lc, lc.to_buf().unwrap().as_lc());
+    }
+
+    #[test]
+    //Row "Circ. desk"
+    fn only_text() {
+        let parse = |i| CallNumber::maybe_parse(i).unwrap().unwrap();
+
+        let expected = CallNumber::Local(Local {
+            entry: "Circ. desk".to_string(),
+            number: None,
+        });
+        assert_eq!(expected, parse("Circ. desk"));
+        assert_eq!(expected, parse("CIRC  DESK"));
+        assert_eq!("Circ. desk", parse("circ desk").to_string());
+        match parse("kit 12") {
+            CallNumber::Local(local) => assert_eq!(Some(12), local.number()),
+            other => panic!("expected a local, got {:?}", other),
+        }
+
+        let (prefixes, locals) = (Prefixes::default(), Locals::default());
+        let (_, repairs) = CallNumber::parse_repairing_with("circ desk", &prefixes, &locals).unwrap();
+        assert_eq!(vec![Repair::NormalizedLocal], repairs);
+        let e = CallNumber::parse_strict_with("circ desk", &prefixes, &locals).unwrap_err();
+        assert_eq!(Some(&Violation::NonCanonicalLocal), e.violation());
+
+        assert!(CallNumber::maybe_parse("Kit twelve").is_err());
+        assert!(CallNumber::maybe_parse("Staff picks").is_err());
+        let mut locals = Locals::none();
+        locals.insert("Staff picks");
+        assert!(CallNumber::maybe_parse_with("staff picks", &prefixes, &locals).is_ok());
+    }
+
+    #[test]
+    fn local_order() {
+        let mut numbers = vec!["Kit 12", "Circ. desk", "QC 5 .D5 2001", "Kit 2", "551.5 CLA"];
+        numbers.sort_by_key(|number| CallNumber::maybe_parse(number).unwrap().unwrap());
+        let expected = vec!["QC 5 .D5 2001", "551.5 CLA", "Circ. desk", "Kit 2", "Kit 12"];
+        assert_eq!(expected, numbers);
+    }
+
+    #[test]
+    fn try_from_str() {
+        use std::convert::TryFrom;
+
+        let lc = LC::try_from("QB 46 .L744 2000").unwrap();
+        assert_eq!("QB 46 .L744 2000", lc.to_string());
+        assert!(LC::try_from("551.5 CLA").is_err());
+        assert!(matches!(CallNumber::try_from("551.5 CLA"), Ok(CallNumber::Dewey(_))));
+        assert!(SuDoc::try_from("I 19.2:G 29").is_ok());
+
+        let buf: LCBuf = "QB 46 .L744 2000".parse().unwrap();
+        assert_eq!("QB 46 .L744 2000", buf.as_str());
+
+        // Only `Parser::parse_repairing` rewrites call numbers
+        let lc = LC::try_from("qc 5 .D5").unwrap();
+        assert_eq!("qc 5 .D5", lc.to_string());
+        let buf: LCBuf = "QB 46 L744 2000".parse().unwrap();
+        assert_eq!("QB 46 L744 2000", buf.as_str());
+        let (lc, repairs) = Parser::new().parse_repairing("QB 46 L744 2000").unwrap();
+        assert_eq!("QB 46 .L744 2000", lc.to_string());
+        assert_eq!(vec![Repair::InsertedDot], repairs);
+
+        let e = LC::try_from("  ").unwrap_err();
+        assert_eq!("missing call number", e.reason());
+    }
+
+    #[test]
+    fn parser_leftover() {
+        let mut parser = Parser::new();
+        assert!(parser.parse_lc("LB 1028 .L43 2000 v.2 teacher's guide").is_ok());
+
+        parser.reject_unrecognized_notes(true);
+        assert!(parser.parse_lc("LB 1028 .L43 2000 v.2 c.3").is_ok());
+        let e = parser.parse_lc("LB 1028 .L43 2000 v.2 teacher's guide").unwrap_err();
+        let expected = Diagnostic {
+            component: Component::Trailing,
+            offset: 22,
+            expected: "the end of the call number".to_string(),
+            found: "teacher's".to_string(),
+        };
+        assert_eq!(Some(expected), e.diagnostic());
+        assert_eq!(
+            r#"unparsed input after the call number in Trailing at "teacher's guide""#,
+            e.reason()
+        );
+    }
+
+    #[test]
+    fn spine_label() {
+        let label = |i, width, wrap| {
+            let mut label = SpineLabel::new();
+            label.width(width).wrap(wrap);
+            label.format(&CallNumber::maybe_parse(i).unwrap().unwrap())
+        };
+
+        assert_eq!(
+            "Ref\nG\n3701\n.S12\n1995\n.U5",
+            label("Ref G 3701 .S12 1995 .U5", 10, Wrap::Words)
+        );
+        assert_eq!(
+            "813.54\nS643w\n2001\nvol. 2\nsuppl.\nindex",
+            label("813.54 S643w 2001 vol. 2 suppl. index", 6, Wrap::Words)
+        );
+        assert_eq!(
+            "QB\n46\n.L744\n2000\nv.2 c.3",
+            label("QB 46 .L744 2000 v.2 c.3 teacher's guide", 8, Wrap::Truncate)
+        );
+        assert_eq!("I 19.2:\nG 29/2001", label("I 19.2:G 29/2001", 10, Wrap::Words));
+    }
 }
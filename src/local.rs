@@ -0,0 +1,114 @@
+use std::fmt;
+
+/// A local shelving location used in place of a classified call number, such
+/// as "Circ. desk" or "Kit 12".
+///
+/// Locals are written back out the way their vocabulary entry is spelled, and
+/// file by that entry and then by number, so "Kit 2" files before "Kit 12".
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Local {
+    pub(crate) entry: String,
+    pub(crate) number: Option<u32>,
+}
+
+/// The vocabulary of local shelving locations the parser recognizes.
+///
+/// Entries match case insensitively, ignoring dots and extra spaces, so
+/// "Circ. desk" also matches "CIRC DESK". A `#` in an entry stands for a
+/// number, so "Kit #" matches "Kit 12".
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Locals {
+    entries: Vec<String>,
+}
+
+impl Local {
+    /// The vocabulary entry this location matched, e.g. "Kit #"
+    pub fn entry(&self) -> &str {
+        &self.entry
+    }
+
+    /// The number matched by the `#` of the entry, if it has one
+    pub fn number(&self) -> Option<u32> {
+        self.number
+    }
+}
+
+impl fmt::Display for Local {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.number {
+            Some(number) => write!(f, "{}", self.entry.replacen('#', &number.to_string(), 1)),
+            None => write!(f, "{}", self.entry),
+        }
+    }
+}
+
+impl Locals {
+    /// A vocabulary that recognizes no local shelving locations at all.
+    pub fn none() -> Self {
+        Locals { entries: vec![] }
+    }
+
+    /// Adds `entry` to the vocabulary, e.g. "Teacher Resource" or "Kit #".
+    pub fn insert(&mut self, entry: impl Into<String>) {
+        let entry = entry.into();
+        let entry = entry.split_whitespace().collect::<Vec<_>>().join(" ");
+
+        if !entry.is_empty() && !self.entries.iter().any(|known| fold(known) == fold(&entry)) {
+            self.entries.push(entry);
+        }
+    }
+
+    /// Matches all of `i` against the vocabulary, also returning whether it
+    /// was already written the way the entry is spelled.
+    pub(crate) fn parse_local(&self, i: &str) -> Option<(Local, bool)> {
+        let words = fold(i);
+
+        self.entries.iter().find_map(|entry| {
+            let pattern = fold(entry);
+            if pattern.len() != words.len() {
+                return None;
+            }
+
+            let mut number = None;
+            for (pattern, word) in pattern.iter().zip(&words) {
+                if pattern == "#" && number.is_none() {
+                    number = Some(word.parse::<u32>().ok()?);
+                } else if pattern != word {
+                    return None;
+                }
+            }
+
+            let local = Local {
+                entry: entry.clone(),
+                number,
+            };
+            let canonical = local.to_string() == i;
+            Some((local, canonical))
+        })
+    }
+}
+
+/// Splits `s` into lowercase words with any dots removed.
+fn fold(s: &str) -> Vec<String> {
+    s.split_whitespace()
+        .map(|word| word.replace('.', "").to_lowercase())
+        .filter(|word| !word.is_empty())
+        .collect()
+}
+
+impl Default for Locals {
+    /// Recognizes "Circ. desk", "Teacher Resource", and "Kit #".
+    fn default() -> Self {
+        let mut locals = Locals::none();
+        locals.extend(vec!["Circ. desk", "Teacher Resource", "Kit #"]);
+        locals
+    }
+}
+
+impl<S: Into<String>> Extend<S> for Locals {
+    fn extend<I: IntoIterator<Item = S>>(&mut self, entries: I) {
+        for entry in entries {
+            self.insert(entry);
+        }
+    }
+}
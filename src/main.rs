@@ -1,18 +1,101 @@
-#![feature(backtrace)]
-
 extern crate csv;
 
 // this result is different. While normally result is an enum, this returns a <T> or an error which is muted.
 use csv::StringRecord;
-use serde::Deserialize;
+use serde::Serialize;
+use std::cmp::Ordering;
+use std::io::{self, Read, Write};
+use std::path::PathBuf;
+use structopt::StructOpt;
 use tracing_subscriber::{prelude::*, registry::Registry};
 use tracing_error::ErrorLayer;
 use spandoc::spandoc;
 
-#[derive(Debug, Deserialize)]
-struct Row {
-    #[serde(rename = "LC")]
-    lc: String,
+/// Normalize the LC, Dewey, and SuDoc call numbers in a library catalog export
+#[derive(Debug, StructOpt)]
+struct Opt {
+    /// CSV file to read, or `-` for stdin
+    #[structopt(parse(from_os_str), default_value = "./exploLibMain.csv")]
+    input: PathBuf,
+
+    /// CSV file to write, or `-` for stdout
+    #[structopt(short, long, parse(from_os_str), default_value = "./exploLibOut.csv")]
+    output: PathBuf,
+
+    /// Header of a column holding call numbers, or its zero based index when
+    /// the file has no headers. May be given more than once, in which case
+    /// shelf order follows the first column and only that one may not be
+    /// blank.
+    #[structopt(
+        short,
+        long = "column",
+        default_value = "LC",
+        number_of_values = 1
+    )]
+    columns: Vec<String>,
+
+    /// Field delimiter of both the input and output files
+    #[structopt(short, long, default_value = ",")]
+    delimiter: char,
+
+    /// Treat the first line of the input as data instead of headers
+    #[structopt(long)]
+    no_headers: bool,
+
+    /// CSV file to write rows whose call numbers need review, such as ones
+    /// with a trailing note
+    #[structopt(long, parse(from_os_str), default_value = "./exploLibReview.csv")]
+    review: PathBuf,
+
+    /// CSV file to write rows whose call numbers are missing or fail to parse
+    #[structopt(long, parse(from_os_str), default_value = "./exploLibRejects.csv")]
+    rejects: PathBuf,
+
+    /// File to write a JSON object per line describing each call number that
+    /// failed to parse
+    #[structopt(long, parse(from_os_str))]
+    diagnostics: Option<PathBuf>,
+
+    /// Add a column with this header to the output listing the corrections
+    /// made to each row's call numbers, so that changed rows can be reviewed
+    #[structopt(long)]
+    repair_column: Option<String>,
+
+    /// Add a column with this header to the output holding a spine label for
+    /// each row, with the components of the first call number column on
+    /// their own lines
+    #[structopt(long)]
+    label_column: Option<String>,
+
+    /// Maximum number of characters on a line of a spine label
+    #[structopt(long, default_value = "10")]
+    label_width: usize,
+
+    /// Cut notes too long for a spine label off after their first line
+    /// instead of wrapping them
+    #[structopt(long)]
+    label_truncate: bool,
+
+    /// Reject call numbers that aren't already in canonical form instead of
+    /// repairing them
+    #[structopt(long, conflicts_with = "repair-column")]
+    strict: bool,
+
+    /// Write normalized rows in shelf order instead of input order
+    #[structopt(long)]
+    shelf_order: bool,
+
+    /// Collection prefix to recognize in front of the class letters, in
+    /// addition to common ones like "Ref" and "Oversize". May be given more
+    /// than once.
+    #[structopt(long = "prefix", number_of_values = 1)]
+    prefixes: Vec<String>,
+
+    /// Local shelving location to keep as is instead of rejecting, in
+    /// addition to "Circ. desk", "Teacher Resource", and "Kit #". A `#`
+    /// stands for a number. May be given more than once.
+    #[structopt(long = "local", number_of_values = 1)]
+    locals: Vec<String>,
 }
 
 #[spandoc]
@@ -24,43 +107,369 @@ fn main() -> Result<(), exploparse::ErrReport> {
 
     tracing::subscriber::set_global_default(subscriber).expect("Could not set global default");
 
-    let mut reader = csv::Reader::from_path("./exploLibMain.csv")?;
-    let mut writer = csv::Writer::from_path("./exploLibOut.csv")?;
+    let opt = Opt::from_args();
+
+    if !opt.delimiter.is_ascii() {
+        return Err(exploparse::ErrReport::msg(format!(
+            "delimiter must be a single ASCII character, got {:?}",
+            opt.delimiter
+        )));
+    }
+
+    let mut prefixes = exploparse::Prefixes::default();
+    prefixes.extend(opt.prefixes.iter().map(String::as_str));
+    let mut locals = exploparse::Locals::default();
+    locals.extend(opt.locals.iter().map(String::as_str));
+
+    let mut parser = exploparse::Parser::new();
+    parser.prefixes(prefixes).locals(locals).strict(opt.strict);
+
+    let mut spine_label = exploparse::SpineLabel::new();
+    spine_label.width(opt.label_width);
+    if opt.label_truncate {
+        spine_label.wrap(exploparse::Wrap::Truncate);
+    }
+
+    let delimiter = opt.delimiter as u8;
+    let has_headers = !opt.no_headers;
+
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .has_headers(has_headers)
+        .from_reader(open_input(&opt.input)?);
+    let mut writer = csv::WriterBuilder::new()
+        .delimiter(delimiter)
+        .from_writer(open_output(&opt.output)?);
+    let mut good_rows = vec![];
     let mut bad_rows = vec![];
     let mut questionable_rows = vec![];
-    let header = reader.headers()?.clone();
-    writer.write_record(&header)?;
+    let header = if has_headers {
+        Some(reader.headers()?.clone())
+    } else {
+        None
+    };
+    let columns = opt
+        .columns
+        .iter()
+        .map(|column| find_column(header.as_ref(), column))
+        .collect::<Result<Vec<_>, _>>()?;
+    if let Some(ref header) = header {
+        let mut output_header = header.clone();
+        if let Some(ref repair_column) = opt.repair_column {
+            output_header.push_field(repair_column);
+        }
+        if let Some(ref label_column) = opt.label_column {
+            output_header.push_field(label_column);
+        }
+        writer.write_record(&output_header)?;
+    }
     let records = reader.records();
 
     for result in records {
         let record = result?;
-        let row: Row = record.deserialize(Some(&header))?;
-        let lc = row.lc.trim();
-
-        /// Normalizing first field of csv data rows
-        match exploparse::LC::maybe_parse(lc) {
-            Ok(Some(lc @ exploparse::LC { note: None, .. })) => {
-                let mut new_record = StringRecord::new();
-                new_record.push_field(&lc.to_string());
-                new_record.extend(record.iter().skip(1));
-                writer.write_record(&new_record)?;
+        let mut status = Status::Good;
+        let mut problem = None;
+        let mut normalized = vec![];
+        let mut repairs = vec![];
+        let mut label = String::new();
+
+        for &column in &columns {
+            let lc = record.get(column).unwrap_or_default().trim();
+            let parsed = if lc.is_empty() {
+                Ok(None)
+            } else {
+                parser.parse_repairing(lc).map(Some)
+            };
+
+            /// Normalizing call number fields of csv data rows
+            let (column_status, reason, diagnostic) = match parsed {
+                Ok(Some((lc, column_repairs)))
+                    if lc.note().is_none_or(exploparse::Note::is_recognized) =>
+                {
+                    normalized.push((column, lc.to_string()));
+                    repairs.extend(column_repairs);
+                    if column == columns[0] {
+                        label = spine_label.format(&lc);
+                    }
+                    continue;
+                }
+                Ok(Some(_)) => (Status::Questionable, "unrecognized trailing note".to_string(), None),
+                Ok(None) if column == columns[0] => (Status::Bad, "missing call number".to_string(), None),
+                // Blank secondary columns are written back out unchanged
+                Ok(None) => continue,
+                Err(e) => {
+                    eprintln!("Error: {:?}\n", e);
+                    (Status::Bad, e.reason(), e.diagnostic())
+                }
+            };
+
+            if column_status > status {
+                status = column_status;
+                problem = Some(Problem {
+                    column,
+                    call_number: lc.to_string(),
+                    reason,
+                    diagnostic,
+                });
             }
-            Ok(Some(_)) => questionable_rows.push(record),
-            Ok(None) => bad_rows.push(record),
-            Err(e) => {
-                eprintln!("Error: {:?}\n", e);
-                bad_rows.push(record);
+        }
+
+        match (status, problem) {
+            (Status::Good, _) | (_, None) => {
+                let mut new_record = replace_fields(&record, &normalized);
+                if opt.repair_column.is_some() {
+                    let repairs = repairs.iter().map(ToString::to_string).collect::<Vec<_>>();
+                    new_record.push_field(&repairs.join("; "));
+                }
+                if opt.label_column.is_some() {
+                    new_record.push_field(&label);
+                }
+                good_rows.push(new_record);
             }
+            (Status::Questionable, Some(problem)) => questionable_rows.push((problem, record)),
+            (Status::Bad, Some(problem)) => bad_rows.push((problem, record)),
         }
     }
 
-    for record in questionable_rows {
-        writer.write_record(&record)?;
+    if opt.shelf_order {
+        /// Sorting good rows into shelf order
+        sort_shelf_order(header.as_ref(), columns[0], &parser, &mut good_rows)?;
     }
 
-    for record in bad_rows {
+    for record in good_rows {
         writer.write_record(&record)?;
     }
 
+    /// Writing rows that need a cataloger's review
+    write_report(&opt.review, delimiter, header.as_ref(), &questionable_rows)?;
+
+    /// Writing rows whose call numbers could not be normalized
+    write_report(&opt.rejects, delimiter, header.as_ref(), &bad_rows)?;
+
+    if let Some(ref path) = opt.diagnostics {
+        /// Writing parse diagnostics for rejected rows
+        write_diagnostics(path, header.as_ref(), &bad_rows)?;
+    }
+
+    writer.flush()?;
+
+    Ok(())
+}
+
+/// How a row is routed to the output, ordered from best to worst so that the
+/// status of a row is the `max` over its call number columns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Status {
+    Good,
+    Questionable,
+    Bad,
+}
+
+/// Why a row was held back from the normalized output.
+#[derive(Debug)]
+struct Problem {
+    column: usize,
+    call_number: String,
+    reason: String,
+    diagnostic: Option<exploparse::Diagnostic>,
+}
+
+impl Problem {
+    fn column_name(&self, header: Option<&StringRecord>) -> String {
+        header
+            .and_then(|header| header.get(self.column))
+            .map(str::to_string)
+            .unwrap_or_else(|| self.column.to_string())
+    }
+}
+
+/// A line of the `--diagnostics` output.
+#[derive(Debug, Serialize)]
+struct DiagnosticRecord<'a> {
+    row: Option<u64>,
+    column: String,
+    call_number: &'a str,
+    #[serde(flatten)]
+    diagnostic: &'a exploparse::Diagnostic,
+}
+
+fn write_diagnostics(
+    path: &PathBuf,
+    header: Option<&StringRecord>,
+    rows: &[(Problem, StringRecord)],
+) -> Result<(), exploparse::ErrReport> {
+    let mut output = io::BufWriter::new(open_output(path)?);
+
+    for (problem, record) in rows {
+        let diagnostic = match problem.diagnostic {
+            Some(ref diagnostic) => diagnostic,
+            None => continue,
+        };
+
+        let line = DiagnosticRecord {
+            row: record.position().map(|position| position.line()),
+            column: problem.column_name(header),
+            call_number: &problem.call_number,
+            diagnostic,
+        };
+
+        serde_json::to_writer(&mut output, &line)?;
+        writeln!(output)?;
+    }
+
+    output.flush()?;
+
     Ok(())
 }
+
+/// Writes held back rows to their own report, prefixing each row with its
+/// line number in the input, the offending column and call number, and the
+/// reason it was held back.
+fn write_report(
+    path: &PathBuf,
+    delimiter: u8,
+    header: Option<&StringRecord>,
+    rows: &[(Problem, StringRecord)],
+) -> Result<(), exploparse::ErrReport> {
+    let mut writer = csv::WriterBuilder::new()
+        .delimiter(delimiter)
+        .flexible(header.is_none())
+        .from_writer(open_output(path)?);
+
+    let mut report_header = StringRecord::from(vec!["Row", "Column", "Call Number", "Reason"]);
+    if let Some(header) = header {
+        report_header.extend(header);
+    }
+    writer.write_record(&report_header)?;
+
+    for (problem, record) in rows {
+        let row = record
+            .position()
+            .map(|position| position.line().to_string())
+            .unwrap_or_default();
+        let column = problem.column_name(header);
+
+        let mut report = StringRecord::from(vec![
+            row,
+            column,
+            problem.call_number.clone(),
+            problem.reason.clone(),
+        ]);
+        report.extend(record);
+        writer.write_record(&report)?;
+    }
+
+    writer.flush()?;
+
+    Ok(())
+}
+
+/// Copies `record`, swapping in the normalized value of each replaced column
+/// and leaving every other column where it was.
+fn replace_fields(record: &StringRecord, replacements: &[(usize, String)]) -> StringRecord {
+    record
+        .iter()
+        .enumerate()
+        .map(|(index, field)| {
+            replacements
+                .iter()
+                .find(|(column, _)| *column == index)
+                .map(|(_, replacement)| replacement.as_str())
+                .unwrap_or(field)
+        })
+        .collect()
+}
+
+fn open_input(path: &PathBuf) -> Result<Box<dyn Read>, exploparse::ErrReport> {
+    if path.as_os_str() == "-" {
+        Ok(Box::new(io::stdin()))
+    } else {
+        Ok(Box::new(std::fs::File::open(path)?))
+    }
+}
+
+fn open_output(path: &PathBuf) -> Result<Box<dyn Write>, exploparse::ErrReport> {
+    if path.as_os_str() == "-" {
+        Ok(Box::new(io::stdout()))
+    } else {
+        Ok(Box::new(std::fs::File::create(path)?))
+    }
+}
+
+/// Resolves a `--column` argument to an index, by header name when the file
+/// has headers and as a plain index when it doesn't.
+fn find_column(header: Option<&StringRecord>, column: &str) -> Result<usize, exploparse::ErrReport> {
+    match header {
+        Some(header) => header
+            .iter()
+            .position(|field| field == column)
+            .ok_or_else(|| exploparse::ErrReport::msg(format!("no column named {:?} in the input headers", column))),
+        None => column
+            .parse()
+            .map_err(|_| {
+                exploparse::ErrReport::msg(format!(
+                    "expected a column index for a file without headers, got {:?}",
+                    column
+                ))
+            }),
+    }
+}
+
+/// Sorts normalized rows into shelf order, breaking ties between copies of
+/// the same call number by the `Copy` and then `Barcode` columns.
+fn sort_shelf_order(
+    header: Option<&StringRecord>,
+    column: usize,
+    parser: &exploparse::Parser,
+    rows: &mut Vec<StringRecord>,
+) -> Result<(), exploparse::ErrReport> {
+    let tiebreak = |name| header.and_then(|header| header.iter().position(|field| field == name));
+    let copy = tiebreak("Copy");
+    let barcode = tiebreak("Barcode");
+
+    let mut keyed = rows
+        .drain(..)
+        .map(|record| {
+            let lc = record.get(column).unwrap_or_default().to_string();
+            (lc, record)
+        })
+        .collect::<Vec<_>>();
+
+    let mut parsed = keyed
+        .iter_mut()
+        .map(|(lc, record)| Ok((parser.parse(lc)?, record)))
+        .collect::<Result<Vec<_>, exploparse::ErrReport>>()?;
+
+    // `sort_by` is stable, so rows that tie on every key keep their input order
+    parsed.sort_by(|(lhs_lc, lhs), (rhs_lc, rhs)| {
+        lhs_lc
+            .cmp(rhs_lc)
+            .then_with(|| compare_column(copy, lhs, rhs))
+            .then_with(|| compare_column(barcode, lhs, rhs))
+    });
+
+    let sorted = parsed
+        .into_iter()
+        .map(|(_, record)| std::mem::take(record))
+        .collect::<Vec<_>>();
+    *rows = sorted;
+
+    Ok(())
+}
+
+/// Compares a column numerically when both values are numbers, falling back to
+/// plain text comparison otherwise.
+fn compare_column(index: Option<usize>, lhs: &StringRecord, rhs: &StringRecord) -> Ordering {
+    let index = match index {
+        Some(index) => index,
+        None => return Ordering::Equal,
+    };
+
+    let lhs = lhs.get(index).unwrap_or_default().trim();
+    let rhs = rhs.get(index).unwrap_or_default().trim();
+
+    match (lhs.parse::<u64>(), rhs.parse::<u64>()) {
+        (Ok(lhs), Ok(rhs)) => lhs.cmp(&rhs),
+        _ => lhs.cmp(rhs),
+    }
+}
@@ -0,0 +1,204 @@
+use crate::{range_end, ErrReport};
+use nom::branch::alt;
+use nom::bytes::complete::{tag_no_case, take_while_m_n};
+use nom::character::complete::{digit1, one_of, space0};
+use nom::combinator::{map_res, opt, value};
+use nom::error::ParseError;
+use nom::sequence::{pair, preceded, tuple};
+use nom::Err;
+use std::fmt;
+use tracing::instrument;
+
+/// The trailing enumeration and chronology of a call number, e.g. "v.2 c.3".
+///
+/// `Display` writes the note back out exactly as it was written, while the
+/// parsed `parts` drive shelf order so that "v.2" files before "v.10".
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Note<'a> {
+    pub(crate) parts: Vec<NotePart<'a>>,
+    pub(crate) text: &'a str,
+}
+
+/// One recognized piece of a `Note`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum NotePart<'a> {
+    /// "v.2", "vol. 2"
+    Volume(u32),
+    /// "pt.3", "part 3"
+    Part(u32),
+    /// "no.4", "number 4"
+    Number(u32),
+    /// "suppl.", "suppl. 2"
+    Supplement(Option<u32>),
+    /// "index"
+    Index,
+    /// "1998", "1998/99", "1990-1991"
+    Years { start: u16, end: Option<u16> },
+    /// "c.2", "copy 2"
+    Copy(u32),
+    /// Text that isn't a recognized designation
+    Other(&'a str),
+}
+
+impl<'a> Note<'a> {
+    #[instrument]
+    pub(crate) fn parse_note(i: &'a str) -> Result<(&'a str, Self), nom::Err<ErrReport>> {
+        let rest = &i[i.len()..];
+        let i = i.trim();
+        if i.is_empty() {
+            let e = ErrReport::from_error_kind(i, nom::error::ErrorKind::Eof);
+            return Err(Err::Error(ErrReport::add_context(i, "Note", e)));
+        }
+
+        let mut parts = vec![];
+        let mut remaining = i;
+
+        while !remaining.is_empty() {
+            let (rest, part) = match NotePart::parse_part(remaining) {
+                Ok((rest, part)) if at_boundary(rest) => (rest, part),
+                _ => {
+                    let end = remaining
+                        .find(char::is_whitespace)
+                        .unwrap_or(remaining.len());
+                    let (other, rest) = remaining.split_at(end);
+                    (rest, NotePart::Other(other))
+                }
+            };
+
+            parts.push(part);
+            remaining = rest.trim_start();
+        }
+
+        Ok((rest, Note { parts, text: i }))
+    }
+
+    /// The recognized pieces of the note, in the order written.
+    pub fn parts(&self) -> &[NotePart<'a>] {
+        &self.parts
+    }
+
+    /// The note as written, with surrounding whitespace trimmed.
+    pub fn as_str(&self) -> &'a str {
+        self.text
+    }
+
+    /// Whether every piece of the note is a recognized designation, meaning
+    /// the call number doesn't need a human to look at it.
+    pub fn is_recognized(&self) -> bool {
+        self.parts
+            .iter()
+            .all(|part| !matches!(part, NotePart::Other(_)))
+    }
+
+    /// Checks whether `i` starts with a volume, part, number, copy, or
+    /// supplement designation or an index, which is where the cutters of a
+    /// call number end even when no date comes first.
+    pub(crate) fn starts_with_designation(i: &str) -> bool {
+        let designation = alt((
+            NotePart::parse_designation,
+            value(NotePart::Index, tag_no_case("index")),
+        ));
+        match designation(i.trim_start()) {
+            Ok((rest, _)) => at_boundary(rest),
+            Err(_) => false,
+        }
+    }
+}
+
+impl<'a> NotePart<'a> {
+    fn parse_part(i: &'a str) -> Result<(&'a str, Self), nom::Err<ErrReport>> {
+        alt((
+            NotePart::parse_designation,
+            NotePart::parse_years,
+            value(NotePart::Index, tag_no_case("index")),
+        ))(i)
+    }
+
+    fn parse_designation(i: &'a str) -> Result<(&'a str, Self), nom::Err<ErrReport>> {
+        let (rest, keyword) = alt((
+            tag_no_case("volume"),
+            tag_no_case("vol."),
+            tag_no_case("v."),
+            tag_no_case("part"),
+            tag_no_case("pt."),
+            tag_no_case("number"),
+            tag_no_case("no."),
+            tag_no_case("copy"),
+            tag_no_case("cop."),
+            tag_no_case("c."),
+            tag_no_case("supplement"),
+            tag_no_case("suppl."),
+            tag_no_case("supp."),
+        ))(i)?;
+        let (rest, number) = opt(preceded(space0, map_res(digit1, str::parse::<u32>)))(rest)?;
+
+        let part = match (keyword.to_ascii_lowercase().as_str(), number) {
+            ("volume" | "vol." | "v.", Some(number)) => NotePart::Volume(number),
+            ("part" | "pt.", Some(number)) => NotePart::Part(number),
+            ("number" | "no.", Some(number)) => NotePart::Number(number),
+            ("copy" | "cop." | "c.", Some(number)) => NotePart::Copy(number),
+            ("supplement" | "suppl." | "supp.", number) => NotePart::Supplement(number),
+            _ => {
+                let e = ErrReport::from_error_kind(rest, nom::error::ErrorKind::Digit);
+                return Err(Err::Error(e));
+            }
+        };
+
+        Ok((rest, part))
+    }
+
+    fn parse_years(i: &'a str) -> Result<(&'a str, Self), nom::Err<ErrReport>> {
+        let year = |min| {
+            map_res(
+                take_while_m_n(min, 4, |c: char| c.is_ascii_digit()),
+                str::parse::<u16>,
+            )
+        };
+        let (i, (start, end)) = tuple((year(4), opt(pair(one_of("-/"), year(2)))))(i)?;
+
+        let end = match end {
+            Some((_, end)) => match range_end(start, end) {
+                Some(end) => Some(end),
+                None => {
+                    let e = ErrReport::from_error_kind(i, nom::error::ErrorKind::Verify);
+                    return Err(Err::Error(e));
+                }
+            },
+            None => None,
+        };
+
+        Ok((i, NotePart::Years { start, end }))
+    }
+}
+
+/// A designation only counts if it isn't immediately followed by more text,
+/// so "v.2a" is left as `Other`.
+fn at_boundary(rest: &str) -> bool {
+    rest.chars().next().map(char::is_whitespace).unwrap_or(true)
+}
+
+impl<'a> fmt::Display for Note<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.text)
+    }
+}
+
+impl<'a> fmt::Display for NotePart<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NotePart::Volume(volume) => write!(f, "v.{}", volume),
+            NotePart::Part(part) => write!(f, "pt.{}", part),
+            NotePart::Number(number) => write!(f, "no.{}", number),
+            NotePart::Supplement(Some(supplement)) => write!(f, "suppl.{}", supplement),
+            NotePart::Supplement(None) => write!(f, "suppl."),
+            NotePart::Index => write!(f, "index"),
+            NotePart::Years {
+                start,
+                end: Some(end),
+            } => write!(f, "{:04}-{:04}", start, end),
+            NotePart::Years { start, end: None } => write!(f, "{:04}", start),
+            NotePart::Copy(copy) => write!(f, "c.{}", copy),
+            NotePart::Other(other) => write!(f, "{}", other),
+        }
+    }
+}
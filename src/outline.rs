@@ -0,0 +1,468 @@
+//! The Library of Congress Classification outline: every class and subclass
+//! that a call number may start with, along with its caption. Also the
+//! National Library of Medicine schedules, which fill the gaps LC leaves in
+//! class Q and the unused class W.
+
+/// Classes and subclasses of the LC outline, sorted by class letters so that
+/// lookups can binary search. Some of the three-letter law subclasses for
+/// individual jurisdictions aren't listed yet, see `omitted_law_subclasses`.
+static OUTLINE: &[(&str, &str)] = &[
+    ("A", "General Works"),
+    ("AC", "Collections. Series. Collected works"),
+    ("AE", "Encyclopedias"),
+    ("AG", "Dictionaries and other general reference works"),
+    ("AI", "Indexes"),
+    ("AM", "Museums. Collectors and collecting"),
+    ("AN", "Newspapers"),
+    ("AP", "Periodicals"),
+    ("AS", "Academies and learned societies"),
+    ("AY", "Yearbooks. Almanacs. Directories"),
+    ("AZ", "History of scholarship and learning. The humanities"),
+    ("B", "Philosophy (General)"),
+    ("BC", "Logic"),
+    ("BD", "Speculative philosophy"),
+    ("BF", "Psychology"),
+    ("BH", "Aesthetics"),
+    ("BJ", "Ethics"),
+    ("BL", "Religions. Mythology. Rationalism"),
+    ("BM", "Judaism"),
+    ("BP", "Islam. Bahai Faith. Theosophy, etc."),
+    ("BQ", "Buddhism"),
+    ("BR", "Christianity"),
+    ("BS", "The Bible"),
+    ("BT", "Doctrinal Theology"),
+    ("BV", "Practical Theology"),
+    ("BX", "Christian Denominations"),
+    ("C", "Auxiliary Sciences of History (General)"),
+    ("CB", "History of Civilization"),
+    ("CC", "Archaeology"),
+    ("CD", "Diplomatics. Archives. Seals"),
+    ("CE", "Technical Chronology. Calendar"),
+    ("CJ", "Numismatics"),
+    ("CN", "Inscriptions. Epigraphy"),
+    ("CR", "Heraldry"),
+    ("CS", "Genealogy"),
+    ("CT", "Biography"),
+    ("D", "History (General)"),
+    ("DA", "Great Britain"),
+    ("DAW", "Central Europe"),
+    ("DB", "Austria - Liechtenstein - Hungary - Czechoslovakia"),
+    ("DC", "France - Andorra - Monaco"),
+    ("DD", "Germany"),
+    ("DE", "Greco-Roman World"),
+    ("DF", "Greece"),
+    ("DG", "Italy - Malta"),
+    ("DH", "Low Countries - Benelux Countries"),
+    ("DJ", "Netherlands (Holland)"),
+    ("DJK", "Eastern Europe (General)"),
+    ("DK", "Russia. Soviet Union. Former Soviet Republics - Poland"),
+    ("DL", "Northern Europe. Scandinavia"),
+    ("DP", "Spain - Portugal"),
+    ("DQ", "Switzerland"),
+    ("DR", "Balkan Peninsula"),
+    ("DS", "Asia"),
+    ("DT", "Africa"),
+    ("DU", "Oceania (South Seas)"),
+    ("DX", "Romanies"),
+    ("E", "History of the Americas"),
+    ("F", "History of the Americas"),
+    ("G", "Geography (General). Atlases. Maps"),
+    ("GA", "Mathematical geography. Cartography"),
+    ("GB", "Physical geography"),
+    ("GC", "Oceanography"),
+    ("GE", "Environmental Sciences"),
+    ("GF", "Human ecology. Anthropogeography"),
+    ("GN", "Anthropology"),
+    ("GR", "Folklore"),
+    ("GT", "Manners and customs (General)"),
+    ("GV", "Recreation. Leisure"),
+    ("H", "Social sciences (General)"),
+    ("HA", "Statistics"),
+    ("HB", "Economic theory. Demography"),
+    ("HC", "Economic history and conditions"),
+    ("HD", "Industries. Land use. Labor"),
+    ("HE", "Transportation and communications"),
+    ("HF", "Commerce"),
+    ("HG", "Finance"),
+    ("HJ", "Public finance"),
+    ("HM", "Sociology (General)"),
+    ("HN", "Social history and conditions. Social problems. Social reform"),
+    ("HQ", "The family. Marriage. Women"),
+    ("HS", "Societies: secret, benevolent, etc."),
+    ("HT", "Communities. Classes. Races"),
+    ("HV", "Social pathology. Social and public welfare. Criminology"),
+    ("HX", "Socialism. Communism. Anarchism"),
+    ("J", "General legislative and executive papers"),
+    ("JA", "Political science (General)"),
+    ("JC", "Political theory"),
+    ("JF", "Political institutions and public administration"),
+    ("JJ", "Political institutions and public administration (North America)"),
+    ("JK", "Political institutions and public administration (United States)"),
+    ("JL", "Political institutions and public administration (Canada, Latin America, etc.)"),
+    ("JN", "Political institutions and public administration (Europe)"),
+    ("JQ", "Political institutions and public administration (Asia, Africa, Australia, Pacific Area, etc.)"),
+    ("JS", "Local government. Municipal government"),
+    ("JV", "Colonies and colonization. Emigration and immigration. International migration"),
+    ("JX", "International law (obsolete)"),
+    ("JZ", "International relations"),
+    ("K", "Law in general. Comparative and uniform law. Jurisprudence"),
+    ("KB", "Religious law in general. Comparative religious law. Jurisprudence"),
+    ("KBM", "Jewish law"),
+    ("KBP", "Islamic law"),
+    ("KBR", "History of canon law"),
+    ("KBS", "Canon law of Eastern churches"),
+    ("KBT", "Canon law of Eastern Rite Churches in Communion with the Holy See of Rome"),
+    ("KBU", "Law of the Roman Catholic Church. The Holy See"),
+    ("KD", "Law of the United Kingdom and Ireland"),
+    ("KDC", "Law of Scotland"),
+    ("KDE", "Law of Northern Ireland"),
+    ("KDG", "Law of the Isle of Man. Channel Islands"),
+    ("KDK", "Law of Ireland (Eire)"),
+    ("KDZ", "Law of America. North America"),
+    ("KE", "Law of Canada"),
+    ("KEA", "Law of Alberta"),
+    ("KEB", "Law of British Columbia"),
+    ("KEM", "Law of Manitoba"),
+    ("KEN", "Law of New Brunswick, Newfoundland, Northwest Territories, and Nova Scotia"),
+    ("KEO", "Law of Ontario"),
+    ("KEP", "Law of Prince Edward Island"),
+    ("KEQ", "Law of Quebec"),
+    ("KES", "Law of Saskatchewan"),
+    ("KEY", "Law of the Yukon"),
+    ("KEZ", "Law of individual Canadian cities"),
+    ("KF", "Law of the United States"),
+    ("KFA", "Law of Alabama, Alaska, Arizona, and Arkansas"),
+    ("KFC", "Law of California, Colorado, and Connecticut"),
+    ("KFD", "Law of Delaware and the District of Columbia"),
+    ("KFF", "Law of Florida"),
+    ("KFG", "Law of Georgia"),
+    ("KFH", "Law of Hawaii"),
+    ("KFI", "Law of Idaho, Illinois, Indiana, and Iowa"),
+    ("KFK", "Law of Kansas and Kentucky"),
+    ("KFL", "Law of Louisiana"),
+    ("KFM", "Law of Maine through Montana"),
+    ("KFN", "Law of Nebraska through North Dakota"),
+    ("KFO", "Law of Ohio, Oklahoma, and Oregon"),
+    ("KFP", "Law of Pennsylvania"),
+    ("KFR", "Law of Rhode Island"),
+    ("KFS", "Law of South Carolina and South Dakota"),
+    ("KFT", "Law of Tennessee and Texas"),
+    ("KFU", "Law of Utah"),
+    ("KFV", "Law of Vermont and Virginia"),
+    ("KFW", "Law of Washington through Wyoming"),
+    ("KFX", "Law of individual cities of the United States"),
+    ("KFZ", "Law of the Northwest Territory and other regions of the United States"),
+    ("KG", "Law of Latin America. Mexico and Central America. West Indies. Caribbean area"),
+    ("KH", "Law of South America"),
+    ("KHA", "Law of Argentina"),
+    ("KHC", "Law of Bolivia"),
+    ("KHD", "Law of Brazil"),
+    ("KHF", "Law of Chile"),
+    ("KHH", "Law of Colombia"),
+    ("KHK", "Law of Ecuador"),
+    ("KHL", "Law of the Falkland Islands"),
+    ("KHM", "Law of French Guiana"),
+    ("KHN", "Law of Guyana"),
+    ("KHP", "Law of Paraguay"),
+    ("KHQ", "Law of Peru"),
+    ("KHS", "Law of Suriname"),
+    ("KHU", "Law of Uruguay"),
+    ("KHW", "Law of Venezuela"),
+    ("KJ", "Law of Europe"),
+    ("KJA", "Roman law"),
+    ("KJC", "Regional comparative and uniform law of Europe"),
+    ("KJE", "Regional organization and integration of Europe"),
+    ("KJG", "Law of Albania"),
+    ("KJH", "Law of Andorra"),
+    ("KJJ", "Law of Austria"),
+    ("KJK", "Law of Belgium"),
+    ("KJM", "Law of Bulgaria"),
+    ("KJN", "Law of Cyprus"),
+    ("KJP", "Law of Czechoslovakia"),
+    ("KJQ", "Law of the Czech Republic"),
+    ("KJR", "Law of Denmark"),
+    ("KJS", "Law of Estonia"),
+    ("KJT", "Law of Finland"),
+    ("KJV", "Law of France"),
+    ("KJW", "Law of the regions and departments of France"),
+    ("KK", "Law of Germany"),
+    ("KKE", "Law of Greece"),
+    ("KKF", "Law of Hungary"),
+    ("KKG", "Law of Iceland"),
+    ("KKH", "Law of Italy"),
+    ("KKT", "Law of Spain"),
+    ("KKW", "Law of Switzerland"),
+    ("KL", "Law of Asia and Eurasia, Africa, Pacific Area, and Antarctica"),
+    ("KLA", "Law of Russia. Soviet Union. Russia (Federation, 1992-)"),
+    ("KM", "Law of Asia"),
+    ("KN", "Law of South Asia, Southeast Asia, and East Asia"),
+    ("KNN", "Law of China (to 1949)"),
+    ("KNP", "Law of Taiwan"),
+    ("KNQ", "Law of China (People's Republic, 1949-)"),
+    ("KNS", "Law of India"),
+    ("KNX", "Law of Japan"),
+    ("KPA", "Law of South Korea"),
+    ("KPC", "Law of North Korea"),
+    ("KQ", "Law of Africa"),
+    ("KU", "Law of the Pacific Area. Australia"),
+    ("KUQ", "Law of New Zealand"),
+    ("KV", "Law of the Pacific Area jurisdictions"),
+    ("KW", "Law of the Pacific Area jurisdictions and Antarctica"),
+    ("KWX", "Law of Antarctica"),
+    ("KZ", "Law of nations"),
+    ("KZA", "Law of the sea"),
+    ("KZD", "Space law. Law of outer space"),
+    ("L", "Education (General)"),
+    ("LA", "History of education"),
+    ("LB", "Theory and practice of education"),
+    ("LC", "Special aspects of education"),
+    ("LD", "Individual institutions - United States"),
+    ("LE", "Individual institutions - America (except United States)"),
+    ("LF", "Individual institutions - Europe"),
+    ("LG", "Individual institutions - Asia, Africa, Indian Ocean islands, Australia, New Zealand, Pacific islands"),
+    ("LH", "College and school magazines and papers"),
+    ("LJ", "Student fraternities and societies, United States"),
+    ("LT", "Textbooks"),
+    ("M", "Music"),
+    ("ML", "Literature on music"),
+    ("MT", "Instruction and study"),
+    ("N", "Visual arts"),
+    ("NA", "Architecture"),
+    ("NB", "Sculpture"),
+    ("NC", "Drawing. Design. Illustration"),
+    ("ND", "Painting"),
+    ("NE", "Print media"),
+    ("NK", "Decorative arts"),
+    ("NX", "Arts in general"),
+    ("P", "Philology. Linguistics"),
+    ("PA", "Greek language and literature. Latin language and literature"),
+    ("PB", "Modern languages. Celtic languages"),
+    ("PC", "Romanic languages"),
+    ("PD", "Germanic languages. Scandinavian languages"),
+    ("PE", "English language"),
+    ("PF", "West Germanic languages"),
+    ("PG", "Slavic languages and literatures. Baltic languages. Albanian language"),
+    ("PH", "Uralic languages. Basque language"),
+    ("PJ", "Oriental languages and literatures"),
+    ("PK", "Indo-Iranian languages and literatures"),
+    ("PL", "Languages and literatures of Eastern Asia, Africa, Oceania"),
+    ("PM", "Hyperborean, Indian, and artificial languages"),
+    ("PN", "Literature (General)"),
+    ("PQ", "French, Italian, Spanish, and Portuguese literature"),
+    ("PR", "English literature"),
+    ("PS", "American literature"),
+    ("PT", "German, Dutch, Flemish, Afrikaans, and Scandinavian literature"),
+    ("PZ", "Fiction and juvenile belles lettres"),
+    ("Q", "Science (General)"),
+    ("QA", "Mathematics"),
+    ("QB", "Astronomy"),
+    ("QC", "Physics"),
+    ("QD", "Chemistry"),
+    ("QE", "Geology"),
+    ("QH", "Natural history - Biology"),
+    ("QK", "Botany"),
+    ("QL", "Zoology"),
+    ("QM", "Human anatomy"),
+    ("QP", "Physiology"),
+    ("QR", "Microbiology"),
+    ("R", "Medicine (General)"),
+    ("RA", "Public aspects of medicine"),
+    ("RB", "Pathology"),
+    ("RC", "Internal medicine"),
+    ("RD", "Surgery"),
+    ("RE", "Ophthalmology"),
+    ("RF", "Otorhinolaryngology"),
+    ("RG", "Gynecology and obstetrics"),
+    ("RJ", "Pediatrics"),
+    ("RK", "Dentistry"),
+    ("RL", "Dermatology"),
+    ("RM", "Therapeutics. Pharmacology"),
+    ("RS", "Pharmacy and materia medica"),
+    ("RT", "Nursing"),
+    ("RV", "Botanic, Thomsonian, and eclectic medicine"),
+    ("RX", "Homeopathy"),
+    ("RZ", "Other systems of medicine"),
+    ("S", "Agriculture (General)"),
+    ("SB", "Plant culture"),
+    ("SD", "Forestry"),
+    ("SF", "Animal culture"),
+    ("SH", "Aquaculture. Fisheries. Angling"),
+    ("SK", "Hunting sports"),
+    ("T", "Technology (General)"),
+    ("TA", "Engineering (General). Civil engineering"),
+    ("TC", "Hydraulic engineering. Ocean engineering"),
+    ("TD", "Environmental technology. Sanitary engineering"),
+    ("TE", "Highway engineering. Roads and pavements"),
+    ("TF", "Railroad engineering and operation"),
+    ("TG", "Bridge engineering"),
+    ("TH", "Building construction"),
+    ("TJ", "Mechanical engineering and machinery"),
+    ("TK", "Electrical engineering. Electronics. Nuclear engineering"),
+    ("TL", "Motor vehicles. Aeronautics. Astronautics"),
+    ("TN", "Mining engineering. Metallurgy"),
+    ("TP", "Chemical technology"),
+    ("TR", "Photography"),
+    ("TS", "Manufactures"),
+    ("TT", "Handicrafts. Arts and crafts"),
+    ("TX", "Home economics"),
+    ("U", "Military science (General)"),
+    ("UA", "Armies: Organization, distribution, military situation"),
+    ("UB", "Military administration"),
+    ("UC", "Maintenance and transportation"),
+    ("UD", "Infantry"),
+    ("UE", "Cavalry. Armor"),
+    ("UF", "Artillery"),
+    ("UG", "Military engineering. Air forces"),
+    ("UH", "Other services"),
+    ("V", "Naval science (General)"),
+    ("VA", "Navies: Organization, distribution, naval situation"),
+    ("VB", "Naval administration"),
+    ("VC", "Naval maintenance"),
+    ("VD", "Naval seamen"),
+    ("VE", "Marines"),
+    ("VF", "Naval ordnance"),
+    ("VG", "Minor services of navies"),
+    ("VK", "Navigation. Merchant marine"),
+    ("VM", "Naval architecture. Shipbuilding. Marine engineering"),
+    ("Z", "Books (General). Writing. Paleography. Book industries and trade. Libraries. Bibliography"),
+    ("ZA", "Information resources (General)"),
+];
+
+/// Looks up the caption of a class or subclass in the LC outline, returning
+/// `None` for class letters that aren't part of it.
+pub fn caption(class: &str) -> Option<&'static str> {
+    OUTLINE
+        .binary_search_by_key(&class, |&(class, _)| class)
+        .ok()
+        .map(|index| OUTLINE[index].1)
+}
+
+/// Schedules of the NLM Classification, sorted by class letters like
+/// `OUTLINE`. None of them are part of the LC outline.
+static NLM_OUTLINE: &[(&str, &str)] = &[
+    ("QS", "Human Anatomy"),
+    ("QT", "Physiology"),
+    ("QU", "Biochemistry. Cell Biology and Genetics"),
+    ("QV", "Pharmacology"),
+    ("QW", "Microbiology. Immunology"),
+    ("QX", "Parasitology. Disease Vectors"),
+    ("QY", "Clinical Laboratory Pathology"),
+    ("QZ", "Pathology. Oncology"),
+    ("W", "Health Professions"),
+    ("WA", "Public Health"),
+    ("WB", "Practice of Medicine"),
+    ("WC", "Communicable Diseases"),
+    ("WD", "Disorders of Systemic, Metabolic or Environmental Origin"),
+    ("WE", "Musculoskeletal System"),
+    ("WF", "Respiratory System"),
+    ("WG", "Cardiovascular System"),
+    ("WH", "Hemic and Lymphatic Systems"),
+    ("WI", "Digestive System"),
+    ("WJ", "Urogenital System"),
+    ("WK", "Endocrine System"),
+    ("WL", "Nervous System"),
+    ("WM", "Psychiatry"),
+    ("WN", "Radiology. Diagnostic Imaging"),
+    ("WO", "Surgery"),
+    ("WP", "Gynecology"),
+    ("WQ", "Obstetrics"),
+    ("WR", "Dermatology"),
+    ("WS", "Pediatrics"),
+    ("WT", "Geriatrics"),
+    ("WU", "Dentistry. Oral Surgery"),
+    ("WV", "Otolaryngology"),
+    ("WW", "Ophthalmology"),
+    ("WX", "Hospitals and Other Health Facilities"),
+    ("WY", "Nursing"),
+    ("WZ", "History of Medicine. Miscellany"),
+];
+
+/// Looks up the caption of a schedule in the NLM Classification, returning
+/// `None` for class letters that aren't part of it.
+pub fn nlm_caption(class: &str) -> Option<&'static str> {
+    NLM_OUTLINE
+        .binary_search_by_key(&class, |&(class, _)| class)
+        .ok()
+        .map(|index| NLM_OUTLINE[index].1)
+}
+
+/// Looks up class letters in either the LC outline or the NLM schedules
+/// ignoring case, returning them as the outline spells them, e.g. "QC" for
+/// "qc".
+pub fn canonical(class: &str) -> Option<&'static str> {
+    let class = class.to_ascii_uppercase();
+
+    OUTLINE
+        .iter()
+        .chain(NLM_OUTLINE)
+        .find(|&&(known, _)| known == class)
+        .map(|&(known, _)| known)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sorted() {
+        assert!(OUTLINE.windows(2).all(|pair| pair[0].0 < pair[1].0));
+        assert!(NLM_OUTLINE.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    }
+
+    #[test]
+    fn disjoint() {
+        assert!(NLM_OUTLINE.iter().all(|&(class, _)| caption(class).is_none()));
+    }
+
+    #[test]
+    fn lookup() {
+        assert_eq!(Some("Physics"), caption("QC"));
+        assert_eq!(Some("Eastern Europe (General)"), caption("DJK"));
+        assert_eq!(None, caption("QX"));
+        assert_eq!(None, caption("ZZ"));
+        assert_eq!(Some("Parasitology. Disease Vectors"), nlm_caption("QX"));
+        assert_eq!(Some("Health Professions"), nlm_caption("W"));
+        assert_eq!(None, nlm_caption("QC"));
+        assert_eq!(Some("QC"), canonical("qc"));
+        assert_eq!(Some("WB"), canonical("Wb"));
+        assert_eq!(None, canonical("qq"));
+    }
+
+    #[test]
+    fn law_subclasses() {
+        for &class in &["KBS", "KHA", "KJJ", "KKT", "KLA", "KNQ", "KNX", "KPA", "KUQ"] {
+            assert!(caption(class).is_some(), "{}", class);
+        }
+    }
+
+    /// Jurisdictions whose three-letter subclasses call numbers are still
+    /// rejected for, by the first and last subclass of each range.
+    #[test]
+    fn omitted_law_subclasses() {
+        let omitted = [
+            ("KGA", "KGZ"), // Mexico, Central America, and the West Indies
+            ("KKA", "KKC"), // Divisions of Germany
+            ("KKI", "KKS"), // Latvia through Romania
+            ("KKV", "KKV"),
+            ("KKX", "KKZ"),
+            ("KLB", "KLW"), // Former Soviet republics
+            ("KMA", "KMZ"), // Middle East
+            ("KNA", "KNM"), // South, Southeast, and East Asia
+            ("KNR", "KNR"),
+            ("KNT", "KNW"),
+            ("KNY", "KNZ"),
+            ("KPB", "KPB"),
+            ("KPD", "KPZ"),
+            ("KQA", "KTZ"), // Africa
+            ("KUA", "KUP"), // Pacific Area
+            ("KUR", "KWW"),
+        ];
+
+        for &(first, last) in &omitted {
+            let listed = OUTLINE
+                .iter()
+                .find(|&&(class, _)| class.len() == 3 && first <= class && class <= last);
+            assert_eq!(None, listed, "{}-{}", first, last);
+        }
+    }
+}
@@ -0,0 +1,214 @@
+use crate::{
+    CallNumber, Dewey, ErrReport, LCBuf, Locals, Note, NotePart, Prefixes, Repair, SuDoc, LC,
+};
+use std::convert::TryFrom;
+use std::str::FromStr;
+
+/// A configured call number parser, for parsing without going through nom.
+///
+/// Every method parses all of its input: anything the parser can't make
+/// sense of is an error rather than being dropped. By default the text after
+/// the last component is kept as a note, and calling
+/// `reject_unrecognized_notes` reports any of it that isn't a recognized
+/// designation as left over instead.
+///
+/// ```
+/// use exploparse::{CallNumber, Parser, Prefixes};
+///
+/// let mut prefixes = Prefixes::default();
+/// prefixes.insert("Atlas");
+///
+/// let mut parser = Parser::new();
+/// parser.prefixes(prefixes).strict(true);
+///
+/// let call_number = parser.parse("Atlas G 1019 .T5 1990").unwrap();
+/// assert!(matches!(call_number, CallNumber::LC(_)));
+/// assert!(parser.parse("Atlas G 1019 T5 1990").is_err());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Parser {
+    prefixes: Prefixes,
+    locals: Locals,
+    strict: bool,
+    reject_unrecognized_notes: bool,
+}
+
+impl Parser {
+    /// A lenient parser recognizing the default prefixes and locals.
+    pub fn new() -> Self {
+        Parser::default()
+    }
+
+    /// Recognizes the collection prefixes in `prefixes` instead of the
+    /// default ones.
+    pub fn prefixes(&mut self, prefixes: Prefixes) -> &mut Self {
+        self.prefixes = prefixes;
+        self
+    }
+
+    /// Recognizes the local shelving locations in `locals` instead of the
+    /// default ones.
+    pub fn locals(&mut self, locals: Locals) -> &mut Self {
+        self.locals = locals;
+        self
+    }
+
+    /// Whether to reject call numbers that aren't already written in
+    /// canonical form instead of repairing them, see `LC::parse_strict`.
+    pub fn strict(&mut self, strict: bool) -> &mut Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Whether to report trailing text that isn't a recognized volume,
+    /// copy, or date designation as left over input instead of keeping it
+    /// as part of the note.
+    pub fn reject_unrecognized_notes(&mut self, reject: bool) -> &mut Self {
+        self.reject_unrecognized_notes = reject;
+        self
+    }
+
+    /// Parses a call number of any scheme.
+    pub fn parse<'a>(&self, i: &'a str) -> Result<CallNumber<'a>, ErrReport> {
+        self.parse_repairing(i).map(|(call_number, _)| call_number)
+    }
+
+    /// Parses a call number of any scheme, also returning the corrections
+    /// made to bring it into canonical form. Strict parsers never make any.
+    pub fn parse_repairing<'a>(
+        &self,
+        i: &'a str,
+    ) -> Result<(CallNumber<'a>, Vec<Repair>), ErrReport> {
+        let i = present(i)?;
+        let parsed = if self.strict {
+            CallNumber::parse_strict_with(i, &self.prefixes, &self.locals)
+                .map(|call_number| (call_number, vec![]))?
+        } else {
+            CallNumber::parse_repairing_with(i, &self.prefixes, &self.locals)?
+        };
+
+        self.check_note(i, parsed.0.note())?;
+        Ok(parsed)
+    }
+
+    /// Parses an LC or NLM call number.
+    pub fn parse_lc<'a>(&self, i: &'a str) -> Result<LC<'a>, ErrReport> {
+        let i = present(i)?;
+        let lc = if self.strict {
+            LC::parse_strict_with(i, &self.prefixes)?
+        } else {
+            LC::parse_repairing_with(i, &self.prefixes)?.0
+        };
+
+        self.check_note(i, lc.note.as_ref())?;
+        Ok(lc)
+    }
+
+    /// Parses a Dewey call number.
+    pub fn parse_dewey<'a>(&self, i: &'a str) -> Result<Dewey<'a>, ErrReport> {
+        let i = present(i)?;
+        let dewey = if self.strict {
+            Dewey::parse_strict_with(i, &self.prefixes)?
+        } else {
+            Dewey::parse_repairing_with(i, &self.prefixes)?.0
+        };
+
+        self.check_note(i, dewey.note.as_ref())?;
+        Ok(dewey)
+    }
+
+    /// Parses a SuDoc call number.
+    pub fn parse_sudoc<'a>(&self, i: &'a str) -> Result<SuDoc<'a>, ErrReport> {
+        let i = present(i)?;
+        if self.strict {
+            SuDoc::parse_strict(i)
+        } else {
+            SuDoc::parse_repairing(i).map(|(sudoc, _)| sudoc)
+        }
+    }
+
+    fn check_note(&self, i: &str, note: Option<&Note<'_>>) -> Result<(), ErrReport> {
+        if !self.reject_unrecognized_notes {
+            return Ok(());
+        }
+
+        let other = note.and_then(|note| {
+            note.parts().iter().find_map(|part| match *part {
+                NotePart::Other(other) => Some(other),
+                _ => None,
+            })
+        });
+
+        match other {
+            // Note parts are slices of `i`, so everything from the first
+            // unrecognized one on is what's left over
+            Some(other) => {
+                let offset = other.as_ptr() as usize - i.as_ptr() as usize;
+                Err(ErrReport::leftover(&i[offset..]).with_input(i))
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+/// Rejects blank input, which every scheme would otherwise report as a
+/// malformed call number.
+fn present(i: &str) -> Result<&str, ErrReport> {
+    if i.trim().is_empty() {
+        Err(ErrReport::msg("missing call number"))
+    } else {
+        Ok(i)
+    }
+}
+
+/// Parses `i` with one of the `maybe_parse` functions, which don't apply any
+/// repairs. `Parser::parse_repairing` applies them and reports what changed.
+fn plain<'a, T>(
+    i: &'a str,
+    parse: impl Fn(&'a str) -> Result<Option<T>, ErrReport>,
+) -> Result<T, ErrReport> {
+    let parsed = parse(present(i)?)?;
+    Ok(parsed.expect("only empty input parses to nothing"))
+}
+
+impl<'a> TryFrom<&'a str> for LC<'a> {
+    type Error = ErrReport;
+
+    fn try_from(i: &'a str) -> Result<Self, Self::Error> {
+        plain(i, LC::maybe_parse)
+    }
+}
+
+impl<'a> TryFrom<&'a str> for Dewey<'a> {
+    type Error = ErrReport;
+
+    fn try_from(i: &'a str) -> Result<Self, Self::Error> {
+        plain(i, Dewey::maybe_parse)
+    }
+}
+
+impl<'a> TryFrom<&'a str> for SuDoc<'a> {
+    type Error = ErrReport;
+
+    fn try_from(i: &'a str) -> Result<Self, Self::Error> {
+        plain(i, SuDoc::maybe_parse)
+    }
+}
+
+impl<'a> TryFrom<&'a str> for CallNumber<'a> {
+    type Error = ErrReport;
+
+    fn try_from(i: &'a str) -> Result<Self, Self::Error> {
+        plain(i, CallNumber::maybe_parse)
+    }
+}
+
+impl FromStr for LCBuf {
+    type Err = ErrReport;
+
+    /// Parses like `LC::try_from`. `LC` borrows from its input, so it can
+    /// only implement `TryFrom<&str>`.
+    fn from_str(i: &str) -> Result<Self, Self::Err> {
+        plain(i, LC::maybe_parse).and_then(LCBuf::try_from)
+    }
+}
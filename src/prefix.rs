@@ -0,0 +1,148 @@
+use crate::ErrReport;
+use nom::error::{context, ErrorKind, ParseError};
+use std::cmp::Ordering;
+use std::fmt;
+
+/// A collection prefix written in front of the class letters, such as "Ref"
+/// in "Ref QC 5 .D5 2001".
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct Prefix<'a>(pub(crate) &'a str);
+
+/// The vocabulary of collection prefixes the parser recognizes.
+///
+/// Prefixes are matched case insensitively as whole words, optionally
+/// followed by a dot, so the default vocabulary accepts "Ref", "REF", and
+/// "Ref." alike.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Prefixes {
+    words: Vec<String>,
+}
+
+impl<'a> Prefix<'a> {
+    /// The prefix as written, e.g. "Ref."
+    pub fn as_str(&self) -> &'a str {
+        self.0
+    }
+
+    /// The prefix without its trailing dot, which is what prefixes file by.
+    fn word(&self) -> &'a str {
+        self.0.trim_end_matches('.')
+    }
+}
+
+impl<'a> PartialOrd for Prefix<'a> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<'a> Ord for Prefix<'a> {
+    /// Groups prefixes regardless of case or a trailing dot, so "Ref" and
+    /// "REF." shelve together.
+    fn cmp(&self, other: &Self) -> Ordering {
+        let upper = |prefix: &Self| prefix.word().to_ascii_uppercase();
+
+        upper(self)
+            .cmp(&upper(other))
+            .then_with(|| self.0.cmp(other.0))
+    }
+}
+
+impl<'a> fmt::Display for Prefix<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Prefixes {
+    /// A vocabulary that recognizes no prefixes at all.
+    pub fn none() -> Self {
+        Prefixes { words: vec![] }
+    }
+
+    /// Adds `word` to the vocabulary, e.g. "Juv Ref" or "Atlas".
+    pub fn insert(&mut self, word: impl Into<String>) {
+        let word = word.into();
+        let word = word.trim().trim_end_matches('.');
+
+        if !word.is_empty() && !self.contains(word) {
+            self.words.push(word.to_string());
+        }
+    }
+
+    /// Whether `word` is part of the vocabulary, ignoring case.
+    pub fn contains(&self, word: &str) -> bool {
+        self.words
+            .iter()
+            .any(|known| known.eq_ignore_ascii_case(word))
+    }
+
+    /// Parses the longest prefix in the vocabulary that `i` starts with,
+    /// along with the spaces separating it from the class letters.
+    pub(crate) fn parse_prefix<'a>(
+        &self,
+        i: &'a str,
+    ) -> Result<(&'a str, Prefix<'a>), nom::Err<ErrReport>> {
+        context("Prefix", |i| self.parse_word(i))(i)
+    }
+
+    fn parse_word<'a>(&self, i: &'a str) -> Result<(&'a str, Prefix<'a>), nom::Err<ErrReport>> {
+        let trimmed = i.trim_start();
+
+        let matched = self
+            .words
+            .iter()
+            .filter_map(|word| {
+                let candidate = trimmed.get(..word.len())?;
+                if !candidate.eq_ignore_ascii_case(word) {
+                    return None;
+                }
+
+                // "Ref." keeps its dot, and either way the prefix has to end
+                // at a space so that "Refs" or "DVDQC" aren't split apart
+                let len = if trimmed[word.len()..].starts_with('.') {
+                    word.len() + 1
+                } else {
+                    word.len()
+                };
+                let rest = &trimmed[len..];
+                if rest.starts_with(char::is_whitespace) {
+                    Some((rest.trim_start(), Prefix(&trimmed[..len])))
+                } else {
+                    None
+                }
+            })
+            .max_by_key(|(_, prefix)| prefix.0.len());
+
+        matched.ok_or_else(|| nom::Err::Error(ErrReport::from_error_kind(i, ErrorKind::Tag)))
+    }
+}
+
+impl Default for Prefixes {
+    /// Recognizes common collection prefixes: "Ref", "Reference", "Oversize",
+    /// "Folio", "Juv", "Juvenile", "DVD", "CD", "Video", and "Periodical".
+    fn default() -> Self {
+        let mut prefixes = Prefixes::none();
+        prefixes.extend(vec![
+            "Ref",
+            "Reference",
+            "Oversize",
+            "Folio",
+            "Juv",
+            "Juvenile",
+            "DVD",
+            "CD",
+            "Video",
+            "Periodical",
+        ]);
+        prefixes
+    }
+}
+
+impl<S: Into<String>> Extend<S> for Prefixes {
+    fn extend<I: IntoIterator<Item = S>>(&mut self, words: I) {
+        for word in words {
+            self.insert(word);
+        }
+    }
+}
@@ -0,0 +1,139 @@
+use crate::{strict, Component, Year};
+use std::fmt;
+
+/// A correction the lenient parser made while reading a call number, so that
+/// rows whose call number text changed can be flagged for a human to review.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Repair {
+    /// "TD224" became "TD 224"
+    InsertedSpace(Component),
+    /// "C43  2005" became "C43 2005"
+    CollapsedSpaces(Component),
+    /// "1695 .55" became "1695.55"
+    RemovedSpaceInClassNumber,
+    /// ". K55" became ".K55"
+    RemovedSpaceAfterDot,
+    /// "224 C3" became "224 .C3"
+    InsertedDot,
+    /// "CIRC DESK" became "Circ. desk"
+    NormalizedLocal,
+    /// "qc" became "QC"
+    UppercasedClass,
+    /// "1990-91" or "1990/1991" became "1990-1991"
+    ExpandedYearRange,
+}
+
+impl fmt::Display for Repair {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Repair::InsertedSpace(component) => write!(f, "inserted missing space before {}", component),
+            Repair::CollapsedSpaces(component) => write!(f, "collapsed repeated spaces before {}", component),
+            Repair::RemovedSpaceInClassNumber => write!(f, "removed space inside class number"),
+            Repair::RemovedSpaceAfterDot => write!(f, "removed space between dot and cutter"),
+            Repair::InsertedDot => write!(f, "inserted missing dot before cutter"),
+            Repair::NormalizedLocal => write!(f, "respelled local shelving location"),
+            Repair::UppercasedClass => write!(f, "uppercased class letters"),
+            Repair::ExpandedYearRange => write!(f, "wrote year range out in full"),
+        }
+    }
+}
+
+/// The raw text each component of an `LC` was parsed from, including any
+/// whitespace the parser skipped on either side.
+#[derive(Debug)]
+pub(crate) struct Spans<'a> {
+    pub(crate) prefix: Option<&'a str>,
+    pub(crate) genre: &'a str,
+    pub(crate) second: &'a str,
+    pub(crate) segments: Vec<(Component, &'a str)>,
+    pub(crate) year: Option<&'a str>,
+    pub(crate) note: Option<&'a str>,
+}
+
+impl<'a> Spans<'a> {
+    /// Compares the raw text of each component against how `Display` would
+    /// write it back out.
+    pub(crate) fn repairs(&self) -> Vec<Repair> {
+        let components = self
+            .prefix
+            .map(|span| (Component::Prefix, span))
+            .into_iter()
+            .chain(Some((Component::Genre, self.genre)))
+            .chain(Some((Component::ClassNumber, self.second)))
+            .chain(self.segments.iter().copied())
+            .chain(self.year.map(|span| (Component::Year, span)))
+            .chain(self.note.map(|span| (Component::Note, span)))
+            .collect::<Vec<_>>();
+        let mut repairs = rewrites(&components);
+
+        if self.genre.contains(char::is_lowercase) {
+            repairs.push(Repair::UppercasedClass);
+        }
+
+        let first_cutter = self.segments.first().map(|(_, span)| span.trim());
+        if !first_cutter.unwrap_or_default().starts_with('.') {
+            repairs.push(Repair::InsertedDot);
+        }
+
+        repairs
+    }
+}
+
+/// Compares the spacing and year ranges of the raw text of each component
+/// against how `Display` would write it back out, for any classification
+/// scheme.
+pub(crate) fn rewrites(components: &[(Component, &str)]) -> Vec<Repair> {
+    let mut repairs = vec![];
+
+    for pair in components.windows(2) {
+        let (_, prev) = pair[0];
+        let (component, next) = pair[1];
+        let gap = trailing_spaces(prev) + leading_spaces(next);
+
+        match gap {
+            0 => repairs.push(Repair::InsertedSpace(component)),
+            1 => (),
+            _ => repairs.push(Repair::CollapsedSpaces(component)),
+        }
+    }
+
+    for &(component, span) in components {
+        let span = span.trim();
+
+        match component {
+            Component::ClassNumber if span.contains(' ') => {
+                repairs.push(Repair::RemovedSpaceInClassNumber)
+            }
+            Component::Cutter if span.starts_with(". ") => repairs.push(Repair::RemovedSpaceAfterDot),
+            Component::Year if expanded(span) => repairs.push(Repair::ExpandedYearRange),
+            _ => (),
+        }
+    }
+
+    // The same fix applied to both cutters is only worth reporting once
+    let mut seen = vec![];
+    repairs.retain(|repair| {
+        let first = !seen.contains(repair);
+        seen.push(*repair);
+        first
+    });
+
+    repairs
+}
+
+/// Whether a year is written back out differently than it was written, which
+/// only happens to ranges like "1990-91" or "1990/1991".
+fn expanded(span: &str) -> bool {
+    match Year::parse_year(span) {
+        Ok((_, year)) => strict::rendering(span, &year).is_err(),
+        Err(_) => false,
+    }
+}
+
+fn leading_spaces(span: &str) -> usize {
+    span.len() - span.trim_start().len()
+}
+
+fn trailing_spaces(span: &str) -> usize {
+    span.len() - span.trim_end().len()
+}
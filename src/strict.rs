@@ -0,0 +1,118 @@
+use crate::{Component, Dewey, Repair, Spans, LC};
+use std::fmt;
+
+/// A way in which a call number deviates from canonical form, reported by
+/// `LC::parse_strict`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Violation {
+    /// "TD224" instead of "TD 224"
+    MissingSpace(Component),
+    /// "C43  2005" instead of "C43 2005"
+    ExtraSpace(Component),
+    /// "1695 .55" instead of "1695.55"
+    SpaceInClassNumber,
+    /// ". K55" instead of ".K55"
+    SpaceAfterDot,
+    /// "224 C3" instead of "224 .C3"
+    MissingDot,
+    /// ".c3" instead of ".C3"
+    LowercaseCutter(String),
+    /// ".CA" instead of something like ".C3"
+    CutterWithoutDigit(String),
+    /// "CIRC DESK" instead of "Circ. desk"
+    NonCanonicalLocal,
+    /// "qc" instead of "QC"
+    LowercaseClass,
+    /// "1990-91" or "1990/1991" instead of "1990-1991"
+    YearRange,
+    /// Anything else written differently than its canonical form, which is
+    /// held here
+    NotCanonical(String),
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Violation::MissingSpace(component) => write!(f, "missing space before {}", component),
+            Violation::ExtraSpace(component) => write!(f, "more than one space before {}", component),
+            Violation::SpaceInClassNumber => write!(f, "space inside class number"),
+            Violation::SpaceAfterDot => write!(f, "space between dot and cutter"),
+            Violation::MissingDot => write!(f, "missing dot before first cutter"),
+            Violation::LowercaseCutter(cutter) => write!(f, "cutter {:?} starts with a lowercase letter", cutter),
+            Violation::CutterWithoutDigit(cutter) => write!(f, "cutter {:?} has no digits", cutter),
+            Violation::NonCanonicalLocal => write!(f, "local shelving location isn't spelled as configured"),
+            Violation::LowercaseClass => write!(f, "class letters aren't uppercase"),
+            Violation::YearRange => write!(f, "year range isn't written out in full with a dash"),
+            Violation::NotCanonical(canonical) => write!(f, "call number should be written {:?}", canonical),
+        }
+    }
+}
+
+impl std::error::Error for Violation {}
+
+impl From<Repair> for Violation {
+    fn from(repair: Repair) -> Self {
+        match repair {
+            Repair::InsertedSpace(component) => Violation::MissingSpace(component),
+            Repair::CollapsedSpaces(component) => Violation::ExtraSpace(component),
+            Repair::RemovedSpaceInClassNumber => Violation::SpaceInClassNumber,
+            Repair::RemovedSpaceAfterDot => Violation::SpaceAfterDot,
+            Repair::InsertedDot => Violation::MissingDot,
+            Repair::NormalizedLocal => Violation::NonCanonicalLocal,
+            Repair::UppercasedClass => Violation::LowercaseClass,
+            Repair::ExpandedYearRange => Violation::YearRange,
+        }
+    }
+}
+
+/// Finds the first way in which a leniently parsed `LC` deviates from how it
+/// would be written canonically, given the input `i` it was parsed from.
+pub(crate) fn check(i: &str, lc: &LC<'_>, spans: &Spans<'_>) -> Result<(), Violation> {
+    if let Some(repair) = spans.repairs().into_iter().next() {
+        return Err(repair.into());
+    }
+
+    for cutter in lc.cutters() {
+        let text = cutter.to_string().trim_start_matches('.').to_string();
+
+        if cutter.letters().starts_with(char::is_lowercase) {
+            return Err(Violation::LowercaseCutter(text));
+        }
+
+        if cutter.digits().is_empty() {
+            return Err(Violation::CutterWithoutDigit(text));
+        }
+    }
+
+    rendering(i, lc)
+}
+
+/// Finds the first way in which a leniently parsed `Dewey` deviates from how
+/// it would be written canonically, given the input `i` it was parsed from
+/// and the repairs it needed.
+pub(crate) fn check_dewey(i: &str, dewey: &Dewey<'_>, repairs: &[Repair]) -> Result<(), Violation> {
+    if let Some(&repair) = repairs.first() {
+        return Err(repair.into());
+    }
+
+    if let Some(ref cutter) = dewey.cutter {
+        if cutter.letters().starts_with(char::is_lowercase) {
+            return Err(Violation::LowercaseCutter(cutter.to_string()));
+        }
+    }
+
+    rendering(i, dewey)
+}
+
+/// Catches any difference between `i` and how `parsed` is written back out
+/// that the more specific checks don't name, such as an abbreviated year
+/// range.
+pub(crate) fn rendering(i: &str, parsed: &impl fmt::Display) -> Result<(), Violation> {
+    let canonical = parsed.to_string();
+
+    if canonical == i {
+        Ok(())
+    } else {
+        Err(Violation::NotCanonical(canonical))
+    }
+}
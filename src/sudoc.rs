@@ -0,0 +1,291 @@
+use crate::{finished, into_report, strict, Component, ErrReport, Repair, Violation};
+use nom::error::{ErrorKind, ParseError};
+use std::cmp::Ordering;
+use std::fmt;
+use tracing::instrument;
+
+/// A parsed Superintendent of Documents call number, such as
+/// "I 19.2:G 29/2001" or "Y 4.AG 8/1:104-45".
+///
+/// The derived ordering is SuDoc filing order: the class stem before the
+/// colon files first, element by element, and a call number that runs out of
+/// elements files before one that continues. So "I 19.2" files before
+/// "I 19.2:G 29", which files before the subseries "I 19.2/2".
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SuDoc<'a> {
+    /// The agency, subagency, and series, e.g. "I 19.2"
+    pub stem: Vec<Element<'a>>,
+    /// Everything after the colon, e.g. "G 29/2001". `Some` but empty when
+    /// the call number ends with its colon.
+    pub book: Option<Vec<Element<'a>>>,
+}
+
+/// A run of letters or digits within a SuDoc call number, along with the
+/// punctuation that separates it from the element before it.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Element<'a> {
+    pub value: Piece<'a>,
+    /// One of '.', '/', '-', or ' ', or `None` for the first element and for
+    /// elements run together like the "2" and "A" of "2A"
+    pub separator: Option<char>,
+}
+
+/// The value of an `Element`. Letters file before numbers at the same
+/// position, letters alphabetically and numbers numerically.
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub enum Piece<'a> {
+    Letters(&'a str),
+    Number(&'a str),
+}
+
+impl<'a> PartialOrd for Piece<'a> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<'a> Ord for Piece<'a> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Piece::Letters(lhs), Piece::Letters(rhs)) => lhs
+                .to_ascii_uppercase()
+                .cmp(&rhs.to_ascii_uppercase())
+                .then_with(|| lhs.cmp(rhs)),
+            (Piece::Number(lhs), Piece::Number(rhs)) => {
+                let trimmed = |s: &'a str| s.trim_start_matches('0');
+                let (lhs_trimmed, rhs_trimmed) = (trimmed(lhs), trimmed(rhs));
+
+                lhs_trimmed
+                    .len()
+                    .cmp(&rhs_trimmed.len())
+                    .then_with(|| lhs_trimmed.cmp(rhs_trimmed))
+                    .then_with(|| lhs.cmp(rhs))
+            }
+            (Piece::Letters(_), Piece::Number(_)) => Ordering::Less,
+            (Piece::Number(_), Piece::Letters(_)) => Ordering::Greater,
+        }
+    }
+}
+
+impl<'a> SuDoc<'a> {
+    pub fn maybe_parse(i: &'a str) -> Result<Option<SuDoc<'a>>, ErrReport> {
+        if i.is_empty() {
+            Ok(None)
+        } else {
+            let (rest, (sudoc, _)) = SuDoc::parse_spanned(i).map_err(into_report)?;
+            finished(i, rest)?;
+            Ok(Some(sudoc))
+        }
+    }
+
+    #[instrument]
+    pub fn parse_sudoc(i: &'a str) -> Result<(&'a str, Self), nom::Err<ErrReport>> {
+        let (i, (sudoc, _)) = SuDoc::parse_spanned(i)?;
+        Ok((i, sudoc))
+    }
+
+    /// Parses a call number the same way `parse_sudoc` does, also returning
+    /// the spacing corrections needed to bring it into canonical form.
+    #[instrument]
+    pub fn parse_repairing(i: &'a str) -> Result<(Self, Vec<Repair>), ErrReport> {
+        let (rest, parsed) = SuDoc::parse_spanned(i).map_err(into_report)?;
+        finished(i, rest)?;
+        Ok(parsed)
+    }
+
+    /// Parses a call number, rejecting anything that isn't already written
+    /// in canonical form.
+    #[instrument]
+    pub fn parse_strict(i: &'a str) -> Result<Self, ErrReport> {
+        let (rest, (sudoc, repairs)) = SuDoc::parse_spanned(i).map_err(into_report)?;
+        finished(i, rest)?;
+
+        match repairs.first() {
+            Some(&repair) => Err(ErrReport::from(Violation::from(repair)).with_input(i)),
+            None => {
+                strict::rendering(i, &sudoc).map_err(|e| ErrReport::from(e).with_input(i))?;
+                Ok(sudoc)
+            }
+        }
+    }
+
+    fn parse_spanned(i: &'a str) -> nom::IResult<&'a str, (Self, Vec<Repair>), ErrReport> {
+        SuDoc::parse_components(i).map_err(|e| match e {
+            nom::Err::Error(e) => nom::Err::Error(e.with_input(i).with_scheme("a SuDoc number")),
+            nom::Err::Failure(e) => {
+                nom::Err::Failure(e.with_input(i).with_scheme("a SuDoc number"))
+            }
+            nom::Err::Incomplete(needed) => nom::Err::Incomplete(needed),
+        })
+    }
+
+    fn parse_components(i: &'a str) -> nom::IResult<&'a str, (Self, Vec<Repair>), ErrReport> {
+        let mut repairs = vec![];
+        let trimmed = i.trim();
+        let (stem, book) = match trimmed.find(':') {
+            Some(colon) => (&trimmed[..colon], Some(&trimmed[colon + 1..])),
+            None => (trimmed, None),
+        };
+
+        // A space before the colon is collapsed like one after it
+        let stem = match book {
+            Some(_) if stem.trim_end().len() != stem.len() => {
+                repairs.push(Repair::CollapsedSpaces(Component::BookNumber));
+                stem.trim_end()
+            }
+            _ => stem,
+        };
+
+        let stem = parse_elements(i, stem, Component::Stem, &mut repairs)?;
+        let is_stem = |stem: &[Element<'_>]| match stem {
+            [agency, number, series, ..] => {
+                matches!(agency.value, Piece::Letters(_))
+                    && matches!(number.value, Piece::Number(_))
+                    && series.separator == Some('.')
+            }
+            _ => false,
+        };
+        if !is_stem(&stem) {
+            let rest = suffix(i, trimmed);
+            let e = ErrReport::from_error_kind(rest, ErrorKind::Verify);
+            return Err(nom::Err::Error(ErrReport::add_context(rest, "Stem", e)));
+        }
+
+        let book = match book {
+            Some(book) => Some(parse_elements(i, book, Component::BookNumber, &mut repairs)?),
+            None => None,
+        };
+
+        Ok((&i[i.len()..], (SuDoc { stem, book }, repairs)))
+    }
+}
+
+/// Splits `i`, the stem or book number of `input`, into runs of letters and
+/// digits, recording the punctuation between them and any spacing that isn't
+/// canonical.
+fn parse_elements<'a>(
+    input: &'a str,
+    i: &'a str,
+    component: Component,
+    repairs: &mut Vec<Repair>,
+) -> Result<Vec<Element<'a>>, nom::Err<ErrReport>> {
+    let context = match component {
+        Component::Stem => "Stem",
+        _ => "BookNumber",
+    };
+    let fail = |rest: &'a str, kind| {
+        let rest = suffix(input, rest);
+        let e = ErrReport::from_error_kind(rest, kind);
+        nom::Err::Error(ErrReport::add_context(rest, context, e))
+    };
+
+    let mut elements: Vec<Element<'a>> = vec![];
+    let mut rest = i.trim_start();
+
+    if rest.len() != i.len() && component == Component::BookNumber {
+        repairs.push(Repair::CollapsedSpaces(component));
+    }
+
+    while !rest.is_empty() {
+        let gap_end = rest
+            .find(|c: char| c.is_ascii_alphanumeric())
+            .unwrap_or(rest.len());
+        let (gap, after) = rest.split_at(gap_end);
+
+        let mut punctuation = gap.chars().filter(|c| !c.is_whitespace());
+        let separator = match (punctuation.next(), punctuation.next()) {
+            (Some(c @ '.'), None) | (Some(c @ '/'), None) | (Some(c @ '-'), None) => Some(c),
+            (None, None) if !gap.is_empty() => Some(' '),
+            (None, None) => None,
+            _ => return Err(fail(rest, ErrorKind::Char)),
+        };
+
+        if after.is_empty() {
+            return Err(fail(rest, ErrorKind::Eof));
+        }
+
+        if elements.is_empty() && separator.is_some() {
+            return Err(fail(rest, ErrorKind::Char));
+        }
+
+        // The agency letters and the number after them are always separated
+        // by a space, e.g. "I 19" rather than "I19"
+        let agency_gap = elements.len() == 1 && component == Component::Stem;
+        let spaces = gap.chars().filter(|c| c.is_whitespace()).count();
+        let separator = match separator {
+            None if agency_gap => {
+                repairs.push(Repair::InsertedSpace(component));
+                Some(' ')
+            }
+            Some(' ') if spaces > 1 => {
+                repairs.push(Repair::CollapsedSpaces(component));
+                Some(' ')
+            }
+            Some(c) if c != ' ' && spaces > 0 => {
+                repairs.push(Repair::CollapsedSpaces(component));
+                Some(c)
+            }
+            separator => separator,
+        };
+
+        let is_digit = after.starts_with(|c: char| c.is_ascii_digit());
+        let end = after
+            .find(|c: char| !c.is_ascii_alphanumeric() || c.is_ascii_digit() != is_digit)
+            .unwrap_or(after.len());
+        let (run, after) = after.split_at(end);
+        let value = if is_digit {
+            Piece::Number(run)
+        } else {
+            Piece::Letters(run)
+        };
+
+        elements.push(Element { value, separator });
+        rest = after;
+    }
+
+    let mut seen = vec![];
+    repairs.retain(|repair| {
+        let first = !seen.contains(repair);
+        seen.push(*repair);
+        first
+    });
+
+    Ok(elements)
+}
+
+/// Extends `part`, a slice of `input`, to the end of `input`, since errors
+/// are located by how much input remains when they're raised.
+fn suffix<'a>(input: &'a str, part: &str) -> &'a str {
+    &input[part.as_ptr() as usize - input.as_ptr() as usize..]
+}
+
+impl<'a> fmt::Display for SuDoc<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for element in &self.stem {
+            write!(f, "{}", element)?;
+        }
+
+        if let Some(ref book) = self.book {
+            write!(f, ":")?;
+
+            for element in book {
+                write!(f, "{}", element)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a> fmt::Display for Element<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(separator) = self.separator {
+            write!(f, "{}", separator)?;
+        }
+
+        match self.value {
+            Piece::Letters(letters) => write!(f, "{}", letters),
+            Piece::Number(number) => write!(f, "{}", number),
+        }
+    }
+}